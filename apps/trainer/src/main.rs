@@ -0,0 +1,103 @@
+use clap::Parser;
+use lib_simulation as sim;
+use rand::{Rng, SeedableRng};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Trains the simulation headless, printing statistics for every generation.
+#[derive(Debug, Parser)]
+struct Args {
+    /// Number of generations to train
+    #[arg(short, long, default_value_t = 100)]
+    generations: usize,
+
+    /// Seed of the run, random when omitted
+    #[arg(short, long)]
+    seed: Option<u64>,
+
+    /// Simulation config, read as JSON for `.json` files and TOML otherwise
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Checkpoint to resume training from
+    #[arg(short, long, conflicts_with_all = ["seed", "config"])]
+    resume: Option<PathBuf>,
+
+    /// Directory the checkpoint and the best brains are saved to
+    #[arg(short, long, default_value = "out")]
+    out: PathBuf,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let (mut simulation, mut rng) = match &args.resume {
+        Some(path) => {
+            let checkpoint = serde_json::from_str(&fs::read_to_string(path)?)?;
+            sim::Simulation::restore::<sim::ChaCha8Rng>(checkpoint)?
+        }
+        None => {
+            let config = match &args.config {
+                Some(path) => load_config(path)?,
+                None => sim::Config::default(),
+            };
+            let seed = args.seed.unwrap_or_else(|| rand::thread_rng().gen());
+            if cfg!(feature = "parallel") {
+                println!("seed {} (parallel breeding, not replayable in the browser)", seed);
+            } else {
+                println!("seed {}", seed);
+            }
+
+            let mut rng = sim::ChaCha8Rng::seed_from_u64(seed);
+            (sim::Simulation::new(&mut rng, config)?, rng)
+        }
+    };
+
+    fs::create_dir_all(&args.out)?;
+    let started = Instant::now();
+
+    for _ in 0..args.generations {
+        let generation_started = Instant::now();
+
+        let statistics = simulation.train_generation(&mut rng);
+        println!(
+            "generation {:>4}  min {:>6.2}  max {:>6.2}  avg {:>6.2}  distance {:>6.3}  unique {:>3}  {:>8.2?}",
+            statistics.generation,
+            statistics.min_fitness,
+            statistics.max_fitness,
+            statistics.avg_fitness,
+            statistics.diversity.mean_distance,
+            statistics.diversity.unique_genotypes,
+            generation_started.elapsed(),
+        );
+
+        save(&args.out, &simulation, &rng)?;
+    }
+
+    println!("trained {} generations in {:.2?}", args.generations, started.elapsed());
+    Ok(())
+}
+
+fn load_config(path: &Path) -> Result<sim::Config, Box<dyn Error>> {
+    let source = fs::read_to_string(path)?;
+    let config = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => sim::Config::from_json(&source)?,
+        _ => sim::Config::from_toml(&source)?,
+    };
+    Ok(config)
+}
+
+fn save(out: &Path, simulation: &sim::Simulation, rng: &sim::ChaCha8Rng) -> Result<(), Box<dyn Error>> {
+    write_atomic(&out.join("checkpoint.json"), &serde_json::to_string(&simulation.checkpoint(rng)?)?)?;
+    write_atomic(&out.join("best_brains.json"), &serde_json::to_string_pretty(simulation.hall_of_fame())?)?;
+    Ok(())
+}
+
+// A crash while writing leaves the previous file intact instead of a truncated one.
+fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    let partial = path.with_extension("json.partial");
+    fs::write(&partial, contents)?;
+    fs::rename(partial, path)
+}
@@ -0,0 +1,80 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryPolicy {
+    Clamp,
+    Reflect,
+    Wrap,
+}
+
+impl BoundaryPolicy {
+    pub fn apply(&self, gene: f32, min: f32, max: f32) -> f32 {
+        if (min..=max).contains(&gene) {
+            return gene;
+        }
+
+        let width = max - min;
+
+        match self {
+            Self::Clamp => gene.clamp(min, max),
+            Self::Reflect => {
+                let offset = (gene - min).rem_euclid(2.0 * width);
+                if offset > width {
+                    max - (offset - width)
+                } else {
+                    min + offset
+                }
+            }
+            Self::Wrap => min + (gene - min).rem_euclid(width),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneBounds {
+    ranges: Vec<(f32, f32)>,
+    policy: BoundaryPolicy,
+}
+
+impl GeneBounds {
+    pub fn uniform(min: f32, max: f32, policy: BoundaryPolicy) -> Self {
+        Self::per_gene(vec![(min, max)], policy)
+    }
+
+    pub fn per_gene(ranges: Vec<(f32, f32)>, policy: BoundaryPolicy) -> Self {
+        assert!(!ranges.is_empty());
+        assert!(ranges.iter().all(|(min, max)| min < max));
+        Self { ranges, policy }
+    }
+
+    pub fn apply(&self, chromosome: &mut Chromosome) {
+        let uniform = self.ranges.len() == 1;
+        assert!(uniform || self.ranges.len() == chromosome.len());
+
+        for (index, gene) in chromosome.iter_mut().enumerate() {
+            let (min, max) = self.ranges[if uniform { 0 } else { index }];
+            *gene = self.policy.apply(*gene, min, max);
+        }
+    }
+}
+
+impl Repair for GeneBounds {
+    fn repair(&self, chromosome: &mut Chromosome) {
+        self.apply(chromosome);
+    }
+}
+
+pub trait Repair<G = f32>: MaybeSendSync {
+    fn repair(&self, chromosome: &mut Chromosome<G>);
+}
+
+impl<G, F> Repair<G> for F
+where
+    F: Fn(&mut Chromosome<G>) + MaybeSendSync,
+{
+    fn repair(&self, chromosome: &mut Chromosome<G>) {
+        self(chromosome)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HallOfFame<G = f32> {
+    capacity: usize,
+    members: Vec<(Chromosome<G>, f32)>,
+}
+
+impl<G> HallOfFame<G> where G: Gene {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            members: vec![],
+        }
+    }
+
+    pub fn members(&self) -> &[(Chromosome<G>, f32)] {
+        &self.members
+    }
+
+    pub(crate) fn record<I>(&mut self, population: &[I])
+    where
+        I: Individual<Gene = G>,
+    {
+        for individual in population {
+            let fitness = individual.fitness();
+            let full = self.members.len() >= self.capacity;
+
+            if self.capacity == 0 || (full && self.members.last().is_some_and(|(_, worst)| fitness <= *worst)) {
+                continue;
+            }
+
+            if self.members.iter().any(|(chromosome, _)| chromosome.distance(individual.chromosome()) == 0.0) {
+                continue;
+            }
+
+            let position = self.members.partition_point(|(_, other)| *other >= fitness);
+            self.members.insert(position, (individual.chromosome().clone(), fitness));
+            self.members.truncate(self.capacity);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Checkpoint<R, G = f32> {
+    pub population: Vec<(Chromosome<G>, f32)>,
+    pub rng: R,
+    generation: usize,
+    births: usize,
+    #[serde(default)]
+    birth_stamps: Vec<usize>,
+    next_id: u64,
+    #[serde(default)]
+    diversity: Diversity,
+    hall_of_fame: HallOfFame<G>,
+    speciation: Option<Speciation<G>>,
+    pareto: ParetoArchive<G>,
+}
+
+impl<S, G> GeneticAlgorithm<S, G> where S: SelectionMethod, G: Gene {
+    pub fn checkpoint<I, R>(&self, population: &[I], rng: &R) -> Checkpoint<R, G>
+    where
+        I: Individual<Gene = G>,
+        R: Clone,
+    {
+        Checkpoint {
+            population: population
+                .iter()
+                .map(|individual| (individual.chromosome().clone(), individual.fitness()))
+                .collect(),
+            rng: rng.clone(),
+            generation: self.generation,
+            births: self.births,
+            birth_stamps: self.birth_stamps.clone(),
+            next_id: self.next_id,
+            diversity: self.diversity.clone(),
+            hall_of_fame: self.hall_of_fame.clone(),
+            speciation: self.speciation.clone(),
+            pareto: self.pareto.clone(),
+        }
+    }
+
+    pub fn restore<R>(&mut self, checkpoint: &Checkpoint<R, G>) {
+        self.generation = checkpoint.generation;
+        self.births = checkpoint.births;
+        self.birth_stamps = checkpoint.birth_stamps.clone();
+        self.next_id = checkpoint.next_id;
+        self.diversity = checkpoint.diversity.clone();
+        self.apply_schedule();
+        self.hall_of_fame = checkpoint.hall_of_fame.clone();
+        self.speciation = checkpoint.speciation.clone();
+        self.pareto = checkpoint.pareto.clone();
+    }
+}
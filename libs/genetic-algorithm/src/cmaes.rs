@@ -0,0 +1,188 @@
+use crate::*;
+use nalgebra::{DMatrix, DVector, SymmetricEigen};
+
+/// Only `GeneticAlgorithm` stamps a `Lineage` on its children; the others
+/// sample from a distribution and leave lineage to the caller.
+pub trait Optimizer<I> {
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I>;
+
+    fn generation(&self) -> usize;
+}
+
+impl<S, G, I> Optimizer<I> for GeneticAlgorithm<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+    I: Individual<Gene = G>,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I> {
+        GeneticAlgorithm::evolve(self, rng, population)
+    }
+
+    fn generation(&self) -> usize {
+        GeneticAlgorithm::generation(self)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CmaEs {
+    initial_sigma: f64,
+    state: Option<CmaState>,
+    generation: usize,
+}
+
+#[derive(Clone, Debug)]
+struct CmaState {
+    mean: DVector<f64>,
+    sigma: f64,
+    covariance: DMatrix<f64>,
+    basis: DMatrix<f64>,
+    scales: DVector<f64>,
+    sigma_path: DVector<f64>,
+    covariance_path: DVector<f64>,
+}
+
+impl CmaEs {
+    pub fn new(sigma: f32) -> Self {
+        assert!(sigma > 0.0);
+        Self {
+            initial_sigma: sigma as f64,
+            state: None,
+            generation: 0,
+        }
+    }
+
+    pub fn mean(&self) -> Option<Vec<f32>> {
+        self.state
+            .as_ref()
+            .map(|state| state.mean.iter().map(|value| *value as f32).collect())
+    }
+
+    pub fn sigma(&self) -> f32 {
+        self.state
+            .as_ref()
+            .map_or(self.initial_sigma, |state| state.sigma) as f32
+    }
+
+    fn tell<I>(&mut self, population: &[I])
+    where
+        I: Individual<Gene = f32>,
+    {
+        let dimension = population[0].chromosome().len();
+        let n = dimension as f64;
+
+        let mut ranked: Vec<&I> = population.iter().collect();
+        ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        let mu = (population.len() / 2).max(1);
+        let raw: Vec<f64> = (1..=mu)
+            .map(|rank| (libm::log(mu as f64 + 0.5) - libm::log(rank as f64)).max(0.0))
+            .collect();
+        let total: f64 = raw.iter().sum();
+        let weights: Vec<f64> = raw.iter().map(|weight| weight / total).collect();
+        let mu_eff = 1.0 / weights.iter().map(|weight| weight * weight).sum::<f64>();
+
+        let points: Vec<DVector<f64>> = ranked[..mu]
+            .iter()
+            .map(|individual| DVector::from_iterator(dimension, individual.chromosome().iter().map(|gene| *gene as f64)))
+            .collect();
+        let recombined = points
+            .iter()
+            .zip(&weights)
+            .fold(DVector::zeros(dimension), |sum, (point, weight)| sum + point * *weight);
+
+        let Some(state) = self.state.as_mut() else {
+            self.state = Some(CmaState {
+                mean: recombined,
+                sigma: self.initial_sigma,
+                covariance: DMatrix::identity(dimension, dimension),
+                basis: DMatrix::identity(dimension, dimension),
+                scales: DVector::from_element(dimension, 1.0),
+                sigma_path: DVector::zeros(dimension),
+                covariance_path: DVector::zeros(dimension),
+            });
+            return;
+        };
+
+        let c_sigma = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let d_sigma = 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma;
+        let c_c = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+        let c_1 = 2.0 / ((n + 1.3) * (n + 1.3) + mu_eff);
+        let c_mu = (1.0 - c_1).min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0) * (n + 2.0) + mu_eff));
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        let old_mean = std::mem::replace(&mut state.mean, recombined);
+        let step = (&state.mean - &old_mean) / state.sigma;
+
+        let inverse_sqrt = &state.basis
+            * DMatrix::from_diagonal(&state.scales.map(|scale| 1.0 / scale))
+            * state.basis.transpose();
+
+        state.sigma_path = &state.sigma_path * (1.0 - c_sigma)
+            + &inverse_sqrt * &step * (c_sigma * (2.0 - c_sigma) * mu_eff).sqrt();
+
+        let sigma_norm = state.sigma_path.norm();
+        let threshold = (1.4 + 2.0 / (n + 1.0)) * chi_n
+            * (1.0 - libm::pow(1.0 - c_sigma, 2.0 * (self.generation + 1) as f64)).sqrt();
+        let h_sigma = if sigma_norm < threshold { 1.0 } else { 0.0 };
+
+        state.covariance_path = &state.covariance_path * (1.0 - c_c)
+            + &step * (h_sigma * (c_c * (2.0 - c_c) * mu_eff).sqrt());
+
+        let rank_mu = points
+            .iter()
+            .zip(&weights)
+            .fold(DMatrix::zeros(dimension, dimension), |sum, (point, weight)| {
+                let y = (point - &old_mean) / state.sigma;
+                sum + &y * y.transpose() * *weight
+            });
+
+        let rank_one = &state.covariance_path * state.covariance_path.transpose();
+        let correction = (1.0 - h_sigma) * c_c * (2.0 - c_c);
+
+        state.covariance = &state.covariance * (1.0 - c_1 - c_mu + c_1 * correction)
+            + rank_one * c_1
+            + rank_mu * c_mu;
+
+        state.sigma *= libm::exp((c_sigma / d_sigma) * (sigma_norm / chi_n - 1.0));
+
+        let covariance = (&state.covariance + state.covariance.transpose()) * 0.5;
+        let eigen = SymmetricEigen::new(covariance);
+        state.basis = eigen.eigenvectors;
+        state.scales = eigen.eigenvalues.map(|value| value.max(f64::EPSILON).sqrt());
+    }
+
+    fn ask<I>(&self, rng: &mut dyn RngCore, count: usize) -> Vec<I>
+    where
+        I: Individual<Gene = f32>,
+    {
+        let state = self.state.as_ref().expect("CMA-ES state must be initialized");
+        let dimension = state.mean.len();
+
+        (0..count)
+            .map(|_| {
+                let z = DVector::from_fn(dimension, |_, _| f64::from(standard_normal(rng)));
+                let y = &state.basis * z.component_mul(&state.scales);
+                let x = &state.mean + y * state.sigma;
+                I::create(x.iter().map(|value| *value as f32).collect())
+            })
+            .collect()
+    }
+}
+
+impl<I> Optimizer<I> for CmaEs
+where
+    I: Individual<Gene = f32>,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I> {
+        assert!(!population.is_empty(), "population must not be empty");
+
+        self.tell(population);
+        self.generation += 1;
+        self.ask(rng, population.len())
+    }
+
+    fn generation(&self) -> usize {
+        self.generation
+    }
+}
@@ -0,0 +1,278 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    Parse(String),
+    Invalid { field: &'static str, reason: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(message) => write!(f, "failed to parse config: {}", message),
+            Self::Invalid { field, reason } => write!(f, "invalid `{}`: {}", field, reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SelectionConfig {
+    #[default]
+    RouletteWheel,
+    Tournament { size: usize },
+}
+
+impl SelectionMethod for SelectionConfig {
+    fn select_index(&self, rng: &mut dyn RngCore, fitness: &[f32]) -> usize {
+        match *self {
+            Self::RouletteWheel => RouletteWheelSelection.select_index(rng, fitness),
+            Self::Tournament { size } => TournamentSelection::new(size).select_index(rng, fitness),
+        }
+    }
+}
+
+impl FromStr for SelectionConfig {
+    type Err = ConfigError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "roulette_wheel" => Ok(Self::RouletteWheel),
+            "tournament" => Ok(Self::Tournament { size: 2 }),
+            _ => Err(ConfigError::Invalid {
+                field: "selection",
+                reason: format!("unknown selection method `{}`", name),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CrossoverConfig {
+    #[default]
+    Uniform,
+    Neuron {
+        #[serde(default)]
+        neurons: Vec<usize>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MutationConfig {
+    Gaussian { chance: f32, coeff: f32 },
+    Creep { chance: f32, coeff: f32 },
+    UniformReset { chance: f32, min: f32, max: f32 },
+    Polynomial { chance: f32, eta: f32, min: f32, max: f32 },
+    SelfAdaptive { initial_sigma: f32, min_sigma: f32, #[serde(default)] per_gene: bool },
+}
+
+impl Default for MutationConfig {
+    fn default() -> Self {
+        Self::Gaussian { chance: 0.01, coeff: 0.3 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduleConfig {
+    Decay { chance: f32, coeff: f32, decay: f32 },
+    Adaptive { chance: f32, coeff: f32, target_diversity: f32, max_boost: f32 },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransformConfig {
+    LinearScaling { multiple: f32 },
+    SigmaTruncation { deviations: f32 },
+    PowerLaw { exponent: f32 },
+    RankNormalization,
+    Windowing,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoundsConfig {
+    pub min: f32,
+    pub max: f32,
+    pub policy: BoundaryPolicy,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GaConfig {
+    pub selection: SelectionConfig,
+    pub crossover: CrossoverConfig,
+    pub mutation: MutationConfig,
+    pub schedule: Option<ScheduleConfig>,
+    pub transforms: Vec<TransformConfig>,
+    pub bounds: Option<BoundsConfig>,
+    pub speciation_threshold: Option<f32>,
+    pub hall_of_fame: usize,
+}
+
+impl GaConfig {
+    pub fn from_toml(source: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(source).map_err(|err| ConfigError::Parse(err.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, ConfigError> {
+        let config: Self = serde_json::from_str(source).map_err(|err| ConfigError::Parse(err.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let SelectionConfig::Tournament { size } = self.selection {
+            check("selection.size", size > 0, "must be positive")?;
+        }
+
+        if let CrossoverConfig::Neuron { neurons } = &self.crossover {
+            check("crossover.neurons", !neurons.is_empty(), "must list at least one neuron")?;
+            check("crossover.neurons", neurons.iter().all(|&genes| genes > 0), "every neuron needs at least one gene")?;
+        }
+
+        match self.mutation {
+            MutationConfig::Gaussian { chance, coeff } | MutationConfig::Creep { chance, coeff } => {
+                probability("mutation.chance", chance)?;
+                check("mutation.coeff", coeff >= 0.0, "must not be negative")?;
+            }
+            MutationConfig::UniformReset { chance, min, max } => {
+                probability("mutation.chance", chance)?;
+                check("mutation.max", min < max, "must be greater than `min`")?;
+            }
+            MutationConfig::Polynomial { chance, eta, min, max } => {
+                probability("mutation.chance", chance)?;
+                check("mutation.eta", eta >= 0.0, "must not be negative")?;
+                check("mutation.max", min < max, "must be greater than `min`")?;
+            }
+            MutationConfig::SelfAdaptive { initial_sigma, min_sigma, .. } => {
+                check("mutation.initial_sigma", initial_sigma > 0.0, "must be positive")?;
+                check("mutation.min_sigma", min_sigma > 0.0, "must be positive")?;
+            }
+        }
+
+        match self.schedule {
+            Some(ScheduleConfig::Decay { chance, coeff, decay }) => {
+                probability("schedule.chance", chance)?;
+                coefficient("schedule.coeff", coeff)?;
+                probability("schedule.decay", decay)?;
+            }
+            Some(ScheduleConfig::Adaptive { chance, coeff, target_diversity, max_boost }) => {
+                probability("schedule.chance", chance)?;
+                coefficient("schedule.coeff", coeff)?;
+                check("schedule.target_diversity", target_diversity > 0.0, "must be positive")?;
+                check("schedule.max_boost", max_boost >= 1.0, "must be at least 1")?;
+            }
+            None => {}
+        }
+
+        for transform in &self.transforms {
+            match *transform {
+                TransformConfig::LinearScaling { multiple } => {
+                    check("transforms.multiple", multiple >= 1.0, "must be at least 1")?
+                }
+                TransformConfig::SigmaTruncation { deviations } => {
+                    check("transforms.deviations", deviations >= 0.0, "must not be negative")?
+                }
+                TransformConfig::PowerLaw { exponent } => {
+                    check("transforms.exponent", exponent > 0.0, "must be positive")?
+                }
+                TransformConfig::RankNormalization | TransformConfig::Windowing => {}
+            }
+        }
+
+        if let Some(bounds) = &self.bounds {
+            check("bounds.max", bounds.min < bounds.max, "must be greater than `min`")?;
+        }
+
+        if let Some(threshold) = self.speciation_threshold {
+            check("speciation_threshold", threshold > 0.0, "must be positive")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn build(&self) -> Result<GeneticAlgorithm<SelectionConfig>, ConfigError> {
+        self.validate()?;
+
+        let mut ga = match &self.crossover {
+            CrossoverConfig::Uniform => GeneticAlgorithm::new(self.selection, UniformCrossover::new(), self.mutation()),
+            CrossoverConfig::Neuron { neurons } => {
+                GeneticAlgorithm::new(self.selection, NeuronCrossover::new(neurons.clone()), self.mutation())
+            }
+        };
+
+        ga = match self.schedule {
+            Some(ScheduleConfig::Decay { chance, coeff, decay }) => {
+                ga.with_mutation_schedule(DecaySchedule::new(chance, coeff, decay))
+            }
+            Some(ScheduleConfig::Adaptive { chance, coeff, target_diversity, max_boost }) => {
+                ga.with_mutation_schedule(AdaptiveSchedule::new(chance, coeff, target_diversity, max_boost))
+            }
+            None => ga,
+        };
+
+        for transform in &self.transforms {
+            ga = match *transform {
+                TransformConfig::LinearScaling { multiple } => ga.with_fitness_transform(LinearScaling::new(multiple)),
+                TransformConfig::SigmaTruncation { deviations } => {
+                    ga.with_fitness_transform(SigmaTruncation::new(deviations))
+                }
+                TransformConfig::PowerLaw { exponent } => ga.with_fitness_transform(PowerLaw::new(exponent)),
+                TransformConfig::RankNormalization => ga.with_fitness_transform(RankNormalization::new()),
+                TransformConfig::Windowing => ga.with_fitness_transform(Windowing::new()),
+            };
+        }
+
+        if let Some(bounds) = &self.bounds {
+            ga = ga.with_bounds(GeneBounds::uniform(bounds.min, bounds.max, bounds.policy));
+        }
+
+        if let Some(threshold) = self.speciation_threshold {
+            ga = ga.with_speciation(Speciation::new(threshold));
+        }
+
+        Ok(ga.with_hall_of_fame(self.hall_of_fame))
+    }
+
+    fn mutation(&self) -> Box<dyn MutationMethod> {
+        match self.mutation {
+            MutationConfig::Gaussian { chance, coeff } => Box::new(GaussianMutation::new(chance, coeff)),
+            MutationConfig::Creep { chance, coeff } => Box::new(CreepMutation::new(chance, coeff)),
+            MutationConfig::UniformReset { chance, min, max } => Box::new(UniformResetMutation::new(chance, min, max)),
+            MutationConfig::Polynomial { chance, eta, min, max } => {
+                Box::new(PolynomialMutation::new(chance, eta, min, max))
+            }
+            MutationConfig::SelfAdaptive { initial_sigma, min_sigma, per_gene: false } => {
+                Box::new(SelfAdaptiveMutation::global(initial_sigma, min_sigma))
+            }
+            MutationConfig::SelfAdaptive { initial_sigma, min_sigma, per_gene: true } => {
+                Box::new(SelfAdaptiveMutation::per_gene(initial_sigma, min_sigma))
+            }
+        }
+    }
+}
+
+pub fn check(field: &'static str, valid: bool, reason: &str) -> Result<(), ConfigError> {
+    if valid {
+        Ok(())
+    } else {
+        Err(ConfigError::Invalid { field, reason: reason.to_string() })
+    }
+}
+
+fn probability(field: &'static str, value: f32) -> Result<(), ConfigError> {
+    check(field, (0.0..=1.0).contains(&value), "must be between 0 and 1")
+}
+
+fn coefficient(field: &'static str, value: f32) -> Result<(), ConfigError> {
+    check(field, value.is_finite() && value >= 0.0, "must be finite and not negative")
+}
@@ -0,0 +1,170 @@
+use crate::*;
+
+pub trait Variation<G = f32> {
+    fn vary(
+        &self,
+        rng: &mut dyn RngCore,
+        target: usize,
+        population: &[&Chromosome<G>],
+        fitness: &[f32],
+    ) -> Chromosome<G>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DifferentialStrategy {
+    Rand1,
+    Best1,
+    CurrentToBest1,
+}
+
+impl DifferentialStrategy {
+    fn donors(&self) -> usize {
+        match self {
+            Self::Rand1 => 3,
+            Self::Best1 | Self::CurrentToBest1 => 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DifferentialMutation {
+    strategy: DifferentialStrategy,
+    weight: f32,
+    crossover_rate: f32,
+}
+
+impl DifferentialMutation {
+    pub fn new(strategy: DifferentialStrategy, weight: f32, crossover_rate: f32) -> Self {
+        assert!((0.0..=2.0).contains(&weight));
+        assert!((0.0..=1.0).contains(&crossover_rate));
+        Self { strategy, weight, crossover_rate }
+    }
+}
+
+impl Default for DifferentialMutation {
+    fn default() -> Self {
+        Self::new(DifferentialStrategy::Rand1, 0.5, 0.9)
+    }
+}
+
+impl Variation for DifferentialMutation {
+    fn vary(
+        &self,
+        rng: &mut dyn RngCore,
+        target: usize,
+        population: &[&Chromosome],
+        fitness: &[f32],
+    ) -> Chromosome {
+        assert!(
+            population.len() > self.strategy.donors(),
+            "population is too small for {:?}",
+            self.strategy,
+        );
+
+        let donors: Vec<&Chromosome> = rand::seq::index::sample(rng, population.len() - 1, self.strategy.donors())
+            .iter()
+            .map(|index| if index >= target { index + 1 } else { index })
+            .map(|index| population[index])
+            .collect();
+
+        let best = population[fitness
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .expect("population must not be empty")];
+
+        let current = population[target];
+        let weight = self.weight;
+        let mutant: Vec<f32> = (0..current.len())
+            .map(|gene| match self.strategy {
+                DifferentialStrategy::Rand1 => {
+                    donors[0].genes[gene] + weight * (donors[1].genes[gene] - donors[2].genes[gene])
+                }
+                DifferentialStrategy::Best1 => {
+                    best.genes[gene] + weight * (donors[0].genes[gene] - donors[1].genes[gene])
+                }
+                DifferentialStrategy::CurrentToBest1 => {
+                    current.genes[gene]
+                        + weight * (best.genes[gene] - current.genes[gene])
+                        + weight * (donors[0].genes[gene] - donors[1].genes[gene])
+                }
+            })
+            .collect();
+
+        let forced = random_index(rng, current.len().max(1));
+        let genes: Vec<f32> = current
+            .iter()
+            .zip(mutant)
+            .enumerate()
+            .map(|(gene, (&current, mutant))| {
+                if gene == forced || rng.gen_bool(self.crossover_rate as f64) {
+                    mutant
+                } else {
+                    current
+                }
+            })
+            .collect();
+
+        Chromosome::new(genes).with_strategy(current.strategy().to_vec())
+    }
+}
+
+pub struct DifferentialEvolution<G = f32> {
+    variation: Box<dyn Variation<G>>,
+    survivors: Vec<(Chromosome<G>, f32)>,
+    generation: usize,
+}
+
+impl<G> DifferentialEvolution<G> where G: Gene {
+    pub fn new(variation: impl Variation<G> + 'static) -> Self {
+        Self {
+            variation: Box::new(variation),
+            survivors: vec![],
+            generation: 0,
+        }
+    }
+
+    fn select<I>(&mut self, population: &[I])
+    where
+        I: Individual<Gene = G>,
+    {
+        if self.survivors.len() != population.len() {
+            self.survivors = population
+                .iter()
+                .map(|individual| (individual.chromosome().clone(), individual.fitness()))
+                .collect();
+            return;
+        }
+
+        for (survivor, trial) in self.survivors.iter_mut().zip(population) {
+            if trial.fitness() >= survivor.1 {
+                *survivor = (trial.chromosome().clone(), trial.fitness());
+            }
+        }
+    }
+}
+
+impl<G, I> Optimizer<I> for DifferentialEvolution<G>
+where
+    G: Gene,
+    I: Individual<Gene = G>,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I> {
+        assert!(!population.is_empty(), "population must not be empty");
+
+        self.select(population);
+        self.generation += 1;
+
+        let parents: Vec<&Chromosome<G>> = self.survivors.iter().map(|(chromosome, _)| chromosome).collect();
+        let fitness: Vec<f32> = self.survivors.iter().map(|(_, fitness)| *fitness).collect();
+
+        (0..parents.len())
+            .map(|target| I::create(self.variation.vary(rng, target, &parents, &fitness)))
+            .collect()
+    }
+
+    fn generation(&self) -> usize {
+        self.generation
+    }
+}
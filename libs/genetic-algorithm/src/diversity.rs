@@ -0,0 +1,129 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+const ENTROPY_BINS: usize = 10;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Diversity {
+    pub mean_distance: f32,
+    pub gene_variance: Vec<f32>,
+    pub entropy: f32,
+    pub unique_genotypes: usize,
+}
+
+impl Diversity {
+    pub fn new<I>(population: &[I]) -> Self
+    where
+        I: Individual,
+    {
+        Self::with_bins(population, ENTROPY_BINS)
+    }
+
+    pub fn with_bins<I>(population: &[I], bins: usize) -> Self
+    where
+        I: Individual,
+    {
+        assert!(bins > 1);
+
+        let chromosomes: Vec<_> = population.iter().map(|individual| individual.chromosome()).collect();
+        let genes = chromosomes.iter().map(|chromosome| chromosome.len()).min().unwrap_or(0);
+        let columns: Vec<Vec<f32>> = (0..genes)
+            .map(|gene| chromosomes.iter().map(|chromosome| chromosome.genes[gene].value()).collect())
+            .collect();
+
+        let entropy = if columns.is_empty() {
+            0.0
+        } else {
+            columns.iter().map(|column| entropy(column, bins)).sum::<f32>() / columns.len() as f32
+        };
+
+        Self {
+            mean_distance: mean_distance(&chromosomes),
+            gene_variance: columns.iter().map(|column| variance(column)).collect(),
+            entropy,
+            unique_genotypes: unique_genotypes(&chromosomes),
+        }
+    }
+
+    pub fn mean_variance(&self) -> f32 {
+        if self.gene_variance.is_empty() {
+            return 0.0;
+        }
+        self.gene_variance.iter().sum::<f32>() / self.gene_variance.len() as f32
+    }
+
+    pub fn mean_deviation(&self) -> f32 {
+        if self.gene_variance.is_empty() {
+            return 0.0;
+        }
+        self.gene_variance.iter().map(|variance| variance.sqrt()).sum::<f32>() / self.gene_variance.len() as f32
+    }
+
+    pub fn is_collapsed(&self, threshold: f32) -> bool {
+        self.unique_genotypes <= 1 || self.mean_distance < threshold
+    }
+}
+
+fn mean_distance<G>(chromosomes: &[&Chromosome<G>]) -> f32
+where
+    G: Gene,
+{
+    let pairs = chromosomes.len() * chromosomes.len().saturating_sub(1) / 2;
+    if pairs == 0 {
+        return 0.0;
+    }
+
+    let total: f32 = chromosomes
+        .iter()
+        .enumerate()
+        .flat_map(|(index, a)| chromosomes[index + 1..].iter().map(move |b| a.distance(b)))
+        .sum();
+
+    total / pairs as f32
+}
+
+fn variance(column: &[f32]) -> f32 {
+    let mean = column.iter().sum::<f32>() / column.len() as f32;
+    column.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / column.len() as f32
+}
+
+// Shannon entropy of the gene's values bucketed into equal-width bins over
+// the observed range, normalised to 0..=1.
+fn entropy(column: &[f32], bins: usize) -> f32 {
+    let min = column.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = column.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max <= min {
+        return 0.0;
+    }
+
+    let mut counts = vec![0usize; bins];
+    for value in column {
+        let bin = (((value - min) / (max - min)) * bins as f32) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+
+    let total = column.len() as f32;
+    let entropy: f32 = counts
+        .into_iter()
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f32 / total;
+            -p * p.ln()
+        })
+        .sum();
+
+    entropy / (bins as f32).ln()
+}
+
+fn unique_genotypes<G>(chromosomes: &[&Chromosome<G>]) -> usize
+where
+    G: Gene,
+{
+    let mut unique: Vec<&Chromosome<G>> = vec![];
+    for chromosome in chromosomes {
+        if !unique.iter().any(|other| other.len() == chromosome.len() && other.distance(chromosome) == 0.0) {
+            unique.push(chromosome);
+        }
+    }
+    unique.len()
+}
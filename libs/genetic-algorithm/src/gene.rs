@@ -0,0 +1,43 @@
+pub trait Gene: Clone + 'static {
+    fn distance(&self, other: &Self) -> f32;
+
+    fn value(&self) -> f32;
+}
+
+impl Gene for f32 {
+    fn distance(&self, other: &Self) -> f32 {
+        (self - other).abs()
+    }
+
+    fn value(&self) -> f32 {
+        *self
+    }
+}
+
+impl Gene for bool {
+    fn distance(&self, other: &Self) -> f32 {
+        if self == other { 0.0 } else { 1.0 }
+    }
+
+    fn value(&self) -> f32 {
+        if *self { 1.0 } else { 0.0 }
+    }
+}
+
+macro_rules! integer_gene {
+    ($($ty:ty),*) => {
+        $(
+            impl Gene for $ty {
+                fn distance(&self, other: &Self) -> f32 {
+                    self.abs_diff(*other) as f32
+                }
+
+                fn value(&self) -> f32 {
+                    *self as f32
+                }
+            }
+        )*
+    };
+}
+
+integer_gene!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
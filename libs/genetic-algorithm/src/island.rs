@@ -0,0 +1,110 @@
+use crate::*;
+
+const ISLAND_IDS: u64 = 1 << 40;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrationTopology {
+    Ring,
+    FullyConnected,
+}
+
+impl MigrationTopology {
+    fn destinations(&self, island: usize, islands: usize) -> Vec<usize> {
+        match self {
+            Self::Ring if islands > 1 => vec![(island + 1) % islands],
+            Self::Ring => vec![],
+            Self::FullyConnected => (0..islands).filter(|&other| other != island).collect(),
+        }
+    }
+}
+
+pub struct IslandModel<S, G = f32> {
+    islands: Vec<GeneticAlgorithm<S, G>>,
+    topology: MigrationTopology,
+    interval: usize,
+    migrants: usize,
+    generation: usize,
+}
+
+impl<S, G> IslandModel<S, G> where S: SelectionMethod, G: Gene {
+    // Each island hands out lineage ids from its own block so they stay unique
+    // across the whole model, which is why founders come from `founder`.
+    pub fn new(islands: Vec<GeneticAlgorithm<S, G>>, topology: MigrationTopology, interval: usize, migrants: usize) -> Self {
+        assert!(!islands.is_empty());
+        assert!(interval > 0);
+
+        let islands = islands
+            .into_iter()
+            .enumerate()
+            .map(|(index, island)| island.with_id_base(index as u64 * ISLAND_IDS))
+            .collect();
+
+        Self {
+            islands,
+            topology,
+            interval,
+            migrants,
+            generation: 0,
+        }
+    }
+
+    pub fn islands(&self) -> &[GeneticAlgorithm<S, G>] {
+        &self.islands
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn founder(&mut self, island: usize) -> Lineage {
+        self.islands[island].founder()
+    }
+
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, populations: &[Vec<I>]) -> Vec<Vec<I>>
+    where
+        I: Individual<Gene = G>,
+    {
+        assert_eq!(populations.len(), self.islands.len());
+
+        let mut offspring: Vec<Vec<I>> = self.islands
+            .iter_mut()
+            .zip(populations)
+            .map(|(island, population)| island.evolve(rng, population))
+            .collect();
+
+        self.generation += 1;
+        if self.generation.is_multiple_of(self.interval) {
+            self.migrate(rng, populations, &mut offspring);
+        }
+
+        offspring
+    }
+
+    fn migrate<I>(&mut self, rng: &mut dyn RngCore, populations: &[Vec<I>], offspring: &mut [Vec<I>])
+    where
+        I: Individual<Gene = G>,
+    {
+        let mut arrivals: Vec<Vec<&I>> = (0..populations.len()).map(|_| vec![]).collect();
+
+        for (island, population) in populations.iter().enumerate() {
+            let mut best: Vec<&I> = population.iter().collect();
+            best.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+            best.truncate(self.migrants);
+
+            for destination in self.topology.destinations(island, populations.len()) {
+                arrivals[destination].extend(best.iter().copied());
+            }
+        }
+
+        for ((population, migrants), island) in offspring.iter_mut().zip(arrivals).zip(&mut self.islands) {
+            let count = migrants.len().min(population.len());
+            let slots = rand::seq::index::sample(rng, population.len(), count);
+
+            for (slot, migrant) in slots.iter().zip(migrants) {
+                let parents = migrant.chromosome().lineage().map(|lineage| vec![lineage.id()]).unwrap_or_default();
+                let lineage = Lineage::new(island.reserve_ids(1), parents);
+                population[slot] = I::create(migrant.chromosome().clone().with_lineage(lineage));
+            }
+        }
+    }
+}
@@ -1,56 +1,366 @@
-#![feature(type_alias_impl_trait)]
+#![feature(impl_trait_in_assoc_type)]
 use rand::{RngCore, Rng};
 use rand::prelude::SliceRandom;
 use rand_chacha::ChaCha8Rng;
 use std::collections::BTreeMap;
 use rand::SeedableRng;
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::{Distribution, WeightedError, WeightedIndex};
+use serde::{Deserialize, Serialize};
 use std::ops::Index;
 
+pub use self::{
+    bounds::*, checkpoint::*, cmaes::*, config::*, differential::*, diversity::*, gene::*, island::*, lineage::*, novelty::*, nsga2::*, runner::*, scaling::*, schedule::*, speciation::*, steady_state::*,
+};
 
+mod bounds;
+mod checkpoint;
+mod cmaes;
+mod config;
+mod differential;
+mod diversity;
+mod gene;
+mod island;
+mod lineage;
+mod novelty;
+mod nsga2;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod runner;
+mod scaling;
+mod schedule;
+mod speciation;
+mod steady_state;
 
-pub struct GeneticAlgorithm<S> {
+// Operators are shared across threads when breeding in parallel, so only
+// then do they have to be `Send + Sync`.
+#[cfg(feature = "parallel")]
+pub trait MaybeSendSync: Send + Sync {}
+#[cfg(feature = "parallel")]
+impl<T> MaybeSendSync for T where T: Send + Sync + ?Sized {}
+
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSendSync {}
+#[cfg(not(feature = "parallel"))]
+impl<T> MaybeSendSync for T where T: ?Sized {}
+
+pub struct GeneticAlgorithm<S, G = f32> {
     selection_method: S,
-    crossover_method: Box<dyn CrossoverMethod>,
-    mutation_method: Box<dyn MutationMethod>,
+    crossover_method: Box<dyn CrossoverMethod<G>>,
+    mutation_method: Box<dyn MutationMethod<G>>,
+    mutation_schedule: Option<Box<dyn MutationSchedule>>,
+    constraints: Vec<Box<dyn Repair<G>>>,
+    fitness_transforms: Vec<Box<dyn FitnessTransform>>,
+    speciation: Option<Speciation<G>>,
+    pareto: ParetoArchive<G>,
+    hall_of_fame: HallOfFame<G>,
+    diversity: Diversity,
+    measured: Option<usize>,
+    generation: usize,
+    births: usize,
+    birth_stamps: Vec<usize>,
+    next_id: u64,
 }
 
-impl<S> GeneticAlgorithm<S> where S: SelectionMethod {
-    pub fn new(selection_method: S, crossover_method: impl CrossoverMethod + 'static, mutation_method: impl MutationMethod + 'static) -> Self {
-        Self { selection_method, crossover_method: Box::new(crossover_method), mutation_method: Box::new(mutation_method) }
+impl<S, G> GeneticAlgorithm<S, G> where S: SelectionMethod, G: Gene {
+    pub fn new(
+        selection_method: S,
+        crossover_method: impl CrossoverMethod<G> + 'static,
+        mutation_method: impl MutationMethod<G> + 'static,
+    ) -> Self {
+        Self {
+            selection_method,
+            crossover_method: Box::new(crossover_method),
+            mutation_method: Box::new(mutation_method),
+            mutation_schedule: None,
+            constraints: vec![],
+            fitness_transforms: vec![],
+            speciation: None,
+            pareto: ParetoArchive::new(),
+            hall_of_fame: HallOfFame::new(0),
+            diversity: Diversity::default(),
+            measured: None,
+            generation: 0,
+            births: 0,
+            birth_stamps: vec![],
+            next_id: 0,
+        }
+    }
+
+    pub fn with_mutation_schedule(mut self, mutation_schedule: impl MutationSchedule + 'static) -> Self {
+        self.mutation_schedule = Some(Box::new(mutation_schedule));
+        self
+    }
+
+    pub fn with_repair(mut self, repair: impl Repair<G> + 'static) -> Self {
+        self.constraints.push(Box::new(repair));
+        self
+    }
+
+    pub fn with_fitness_transform(mut self, transform: impl FitnessTransform + 'static) -> Self {
+        self.fitness_transforms.push(Box::new(transform));
+        self
+    }
+
+    pub fn with_speciation(mut self, speciation: Speciation<G>) -> Self {
+        self.speciation = Some(speciation);
+        self
+    }
+
+    pub fn selection_method(&self) -> &S {
+        &self.selection_method
+    }
+
+    pub fn set_selection_method(&mut self, selection_method: S) {
+        self.selection_method = selection_method;
+    }
+
+    pub fn with_hall_of_fame(mut self, capacity: usize) -> Self {
+        self.hall_of_fame = HallOfFame::new(capacity);
+        self
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn hall_of_fame(&self) -> &HallOfFame<G> {
+        &self.hall_of_fame
+    }
+
+    // Only measured when the mutation schedule asks for it or a caller hands
+    // it over through `observe_diversity`.
+    pub fn diversity(&self) -> &Diversity {
+        &self.diversity
+    }
+
+    /// Hands over the diversity of the population about to be evolved, so a
+    /// schedule doesn't measure it a second time this generation.
+    pub fn observe_diversity(&mut self, diversity: Diversity) {
+        self.diversity = diversity;
+        self.measured = Some(self.generation);
+    }
+
+    pub fn pareto_front(&self) -> impl Iterator<Item = (&Chromosome<G>, &[f32])> {
+        self.pareto.front()
+    }
+
+    pub fn species(&self) -> &[usize] {
+        self.speciation
+            .as_ref()
+            .map(|speciation| speciation.assignments())
+            .unwrap_or_default()
     }
     
     pub fn evolve<I>(
-        &self,
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I]
+    ) -> Vec<I> where I: Individual<Gene = G> {
+        let fitness: Vec<f32> = population.iter().map(|individual| individual.fitness()).collect();
+        self.evolve_scored(rng, population, &fitness)
+    }
+
+    pub fn evolve_scored<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        scores: &[f32]
+    ) -> Vec<I> where I: Individual<Gene = G> {
+        assert_eq!(population.len(), scores.len());
+
+        let (pools, slots) = self.prepare(population, scores.to_vec());
+        let parents: Vec<_> = population.iter().map(|individual| individual.chromosome()).collect();
+        let first_id = self.reserve_ids(slots.len());
+
+        let offspring = slots
+            .iter()
+            .enumerate()
+            .map(|(slot, &pool)| self.spawn(rng, &parents, &pools[pool], first_id + slot as u64))
+            .collect();
+
+        self.generation += 1;
+        offspring
+    }
+
+    pub fn evolve_multi_objective<I>(
+        &mut self,
         rng: &mut dyn RngCore,
         population: &[I]
-    ) -> Vec<I> where I: Individual {
-        (0..population.len())
-            .map(|_| {
-                let parent_a = self.selection_method.select(rng, population).chromosome();
-                let parent_b = self.selection_method.select(rng, population).chromosome();
-                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
-                self.mutation_method.mutate(rng, &mut child);
-                I::create(child)
+    ) -> Vec<I> where I: Individual<Gene = G> + MultiObjective {
+        self.record(population);
+
+        let candidates = population
+            .iter()
+            .map(|individual| (individual.chromosome().clone(), individual.objectives()))
+            .collect();
+
+        // Parents are drawn from the Pareto archive, so the pools are built
+        // over the survivors rather than the population. Their scores encode
+        // rank and crowding, which scalar transforms and the configured
+        // selection method would distort, so NSGA-II's own tournament picks
+        // from them as they are.
+        let scores = self.pareto.survive(candidates, population.len());
+        let survivors: Vec<I> = self.pareto.chromosomes().map(|chromosome| I::create(chromosome.clone())).collect();
+        let (pools, slots) = self.breeding_pools(&survivors, scores, true);
+
+        let parents: Vec<_> = survivors.iter().map(|individual| individual.chromosome()).collect();
+        let first_id = self.reserve_ids(slots.len());
+
+        let offspring = slots
+            .iter()
+            .enumerate()
+            .map(|(slot, &pool)| self.spawn_with(rng, &Nsga2Selection, &parents, &pools[pool], first_id + slot as u64))
+            .collect();
+
+        self.generation += 1;
+        offspring
+    }
+
+    fn prepare<I>(&mut self, population: &[I], fitness: Vec<f32>) -> (Vec<BreedingPool>, Vec<usize>)
+    where
+        I: Individual<Gene = G>,
+    {
+        self.record(population);
+        let fitness = self.transform(fitness);
+        self.breeding_pools(population, fitness, true)
+    }
+
+    // Once-per-generation bookkeeping, kept apart from the pools so steady-state
+    // births don't redo it for every single child.
+    fn record<I>(&mut self, population: &[I])
+    where
+        I: Individual<Gene = G>,
+    {
+        let needs_diversity = self.mutation_schedule.as_ref().is_some_and(|schedule| schedule.uses_diversity());
+        if needs_diversity && self.measured != Some(self.generation) {
+            self.observe_diversity(Diversity::new(population));
+        }
+        self.apply_schedule();
+        self.hall_of_fame.record(population);
+    }
+
+    fn apply_schedule(&mut self) {
+        if let Some(schedule) = &self.mutation_schedule {
+            let (chance, coeff) = schedule.rates(self.generation, &self.diversity);
+            self.mutation_method.set_rates(chance, coeff);
+        }
+    }
+
+    fn transform(&self, fitness: Vec<f32>) -> Vec<f32> {
+        self.fitness_transforms
+            .iter()
+            .fold(fitness, |fitness, transform| transform.transform(&fitness))
+    }
+
+    fn breeding_pools<I>(&mut self, population: &[I], fitness: Vec<f32>, respeciate: bool) -> (Vec<BreedingPool>, Vec<usize>)
+    where
+        I: Individual<Gene = G>,
+    {
+        let Some(speciation) = self.speciation.as_mut() else {
+            let pool = BreedingPool {
+                members: (0..population.len()).collect(),
+                fitness,
+            };
+            return (vec![pool], vec![0; population.len()]);
+        };
+
+        let species = if respeciate {
+            speciation.speciate(population)
+        } else {
+            speciation.classify(population)
+        };
+        let shared = share_fitness(&species, &fitness);
+        let quotas = allocate_offspring(&species, &shared, population.len());
+
+        let slots = quotas
+            .iter()
+            .enumerate()
+            .flat_map(|(pool, &quota)| std::iter::repeat_n(pool, quota))
+            .collect();
+
+        let pools = species
+            .into_iter()
+            .map(|members| BreedingPool {
+                fitness: members.iter().map(|&index| shared[index]).collect(),
+                members,
             })
-            .collect()
+            .collect();
+
+        (pools, slots)
+    }
+
+    fn spawn<I>(&self, rng: &mut dyn RngCore, parents: &[&Chromosome<G>], pool: &BreedingPool, id: u64) -> I
+    where
+        I: Individual<Gene = G>,
+    {
+        self.spawn_with(rng, &self.selection_method, parents, pool, id)
+    }
+
+    fn spawn_with<I, M>(&self, rng: &mut dyn RngCore, selection: &M, parents: &[&Chromosome<G>], pool: &BreedingPool, id: u64) -> I
+    where
+        I: Individual<Gene = G>,
+        M: SelectionMethod,
+    {
+        let parent_a = parents[pool.members[selection.select_index(rng, &pool.fitness)]];
+        let parent_b = parents[pool.members[selection.select_index(rng, &pool.fitness)]];
+
+        let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+        self.constrain(&mut child);
+        self.mutation_method.mutate(rng, &mut child);
+        self.constrain(&mut child);
+        child.lineage = Some(Lineage::descend(id, parent_a, parent_b));
+        I::create(child)
+    }
+
+    fn constrain(&self, child: &mut Chromosome<G>) {
+        for constraint in &self.constraints {
+            constraint.repair(child);
+        }
+    }
+}
+
+struct BreedingPool {
+    members: Vec<usize>,
+    fitness: Vec<f32>,
+}
+
+impl<S> GeneticAlgorithm<S> where S: SelectionMethod {
+    pub fn with_bounds(self, bounds: GeneBounds) -> Self {
+        self.with_repair(bounds)
     }
 }
 
 pub trait SelectionMethod {
+    fn select_index(&self, rng: &mut dyn RngCore, fitness: &[f32]) -> usize;
+
     fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
     where
-        I: Individual;
+        Self: Sized,
+        I: Individual,
+    {
+        let fitness: Vec<f32> = population.iter().map(|individual| individual.fitness()).collect();
+        &population[self.select_index(rng, &fitness)]
+    }
+}
+
+impl<S> SelectionMethod for Box<S> where S: SelectionMethod + ?Sized {
+    fn select_index(&self, rng: &mut dyn RngCore, fitness: &[f32]) -> usize {
+        (**self).select_index(rng, fitness)
+    }
 }
 
 
 pub trait Individual {
+    type Gene: Gene;
+
     fn fitness(&self) -> f32;
-    fn chromosome(&self) -> &Chromosome;
-    fn create(chromosome: Chromosome) -> Self;
+    fn chromosome(&self) -> &Chromosome<Self::Gene>;
+    fn create(chromosome: Chromosome<Self::Gene>) -> Self;
 }
 
-pub trait CrossoverMethod {
-    fn crossover(&self, rng: &mut dyn RngCore, parent_a: &Chromosome, parent_b: &Chromosome) -> Chromosome;
+pub trait CrossoverMethod<G = f32>: MaybeSendSync {
+    fn crossover(&self, rng: &mut dyn RngCore, parent_a: &Chromosome<G>, parent_b: &Chromosome<G>) -> Chromosome<G>;
 }
 
 pub struct UniformCrossover;
@@ -61,25 +371,17 @@ impl UniformCrossover {
     }
 }
 
-impl CrossoverMethod for UniformCrossover {
+impl<G> CrossoverMethod<G> for UniformCrossover where G: Clone {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome
-    ) -> Chromosome {
-        let genes = parent_a
-            .iter()
-            .zip(parent_b.iter())
-            .map(|(gene_a, gene_b)| {
-                if rng.gen_bool(0.5) {
-                    *gene_a
-                } else {
-                    *gene_b
-                }
-            })
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>
+    ) -> Chromosome<G> {
+        let mask: Vec<bool> = (0..parent_a.len().min(parent_b.len()))
+            .map(|_| rng.gen_bool(0.5))
             .collect();
-        Chromosome::new(genes)
+        Chromosome::combine(parent_a, parent_b, &mask)
     }
 }
 
@@ -89,6 +391,38 @@ impl Default for UniformCrossover {
     }
 }
 
+pub struct NeuronCrossover {
+    neurons: Vec<usize>,
+}
+
+impl NeuronCrossover {
+    pub fn new(neurons: Vec<usize>) -> Self {
+        assert!(neurons.iter().all(|&genes| genes > 0));
+        Self { neurons }
+    }
+}
+
+impl<G> CrossoverMethod<G> for NeuronCrossover where G: Clone {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+        assert_eq!(parent_a.len(), self.neurons.iter().sum::<usize>());
+
+        let mut mask = Vec::with_capacity(parent_a.len());
+
+        for &neuron in &self.neurons {
+            let from_a = rng.gen_bool(0.5);
+            mask.extend(std::iter::repeat_n(from_a, neuron));
+        }
+
+        Chromosome::combine(parent_a, parent_b, &mask)
+    }
+}
+
 pub struct RouletteWheelSelection;
 
 impl RouletteWheelSelection {
@@ -98,18 +432,13 @@ impl RouletteWheelSelection {
 }
 
 impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I>(
-        &self,
-        rng: &mut dyn RngCore,
-        population: &'a [I],
-    ) -> &'a I 
-    where 
-        I: Individual,
-        {
-            population
-                .choose_weighted(rng, |individual| individual.fitness())
-                .expect("population must not be empty")
+    fn select_index(&self, rng: &mut dyn RngCore, fitness: &[f32]) -> usize {
+        match WeightedIndex::new(fitness) {
+            Ok(distribution) => distribution.sample(rng),
+            Err(WeightedError::AllWeightsZero) => random_index(rng, fitness.len()),
+            Err(_) => panic!("population must not be empty"),
         }
+    }
 }
 
 
@@ -119,36 +448,123 @@ impl Default for RouletteWheelSelection {
     }
 }
 
-#[derive(Debug)]
-pub struct Chromosome {
-    genes: Vec<f32>,
+#[derive(Clone, Copy, Debug)]
+pub struct TournamentSelection {
+    size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0);
+        Self { size }
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select_index(&self, rng: &mut dyn RngCore, fitness: &[f32]) -> usize {
+        assert!(!fitness.is_empty(), "population must not be empty");
+
+        (0..self.size)
+            .map(|_| random_index(rng, fitness.len()))
+            .reduce(|best, other| if fitness[other] > fitness[best] { other } else { best })
+            .expect("tournament size is positive")
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Chromosome<G = f32> {
+    genes: Vec<G>,
+    strategy: Vec<f32>,
+    #[serde(default)]
+    lineage: Option<Lineage>,
 }
 
-impl Chromosome {
-    pub fn new(genes: Vec<f32>) -> Self {
-        Self { genes }
+impl<G> Chromosome<G> {
+    pub fn new(genes: Vec<G>) -> Self {
+        Self { genes, strategy: vec![], lineage: None }
+    }
+
+    pub fn with_strategy(mut self, strategy: Vec<f32>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn strategy(&self) -> &[f32] {
+        &self.strategy
+    }
+
+    pub fn with_lineage(mut self, lineage: Lineage) -> Self {
+        self.lineage = Some(lineage);
+        self
+    }
+
+    pub fn lineage(&self) -> Option<&Lineage> {
+        self.lineage.as_ref()
+    }
+
+    pub(crate) fn combine(parent_a: &Chromosome<G>, parent_b: &Chromosome<G>, mask: &[bool]) -> Self
+    where
+        G: Clone,
+    {
+        fn pick<T: Clone>(mask: &[bool], a: &[T], b: &[T]) -> Vec<T> {
+            mask.iter()
+                .zip(a.iter().zip(b))
+                .map(|(from_a, (gene_a, gene_b))| if *from_a { gene_a.clone() } else { gene_b.clone() })
+                .collect()
+        }
+
+        let genes = pick(mask, &parent_a.genes, &parent_b.genes);
+
+        let strategy = if parent_a.strategy.len() != parent_b.strategy.len() {
+            vec![]
+        } else if parent_a.strategy.len() == parent_a.genes.len() {
+            pick(mask, &parent_a.strategy, &parent_b.strategy)
+        } else {
+            parent_a.strategy
+                .iter()
+                .zip(&parent_b.strategy)
+                .map(|(sigma_a, sigma_b)| (sigma_a + sigma_b) / 2.0)
+                .collect()
+        };
+
+        Self { genes, strategy, lineage: None }
     }
     
     pub fn len(&self) -> usize {
         self.genes.len()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
         self.genes.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
         self.genes.iter_mut()
     }
     pub fn is_empty(&self) -> bool {
         self.genes.is_empty()
     }
+
+    pub fn distance(&self, other: &Self) -> f32
+    where
+        G: Gene,
+    {
+        if self.genes.is_empty() {
+            return 0.0;
+        }
+
+        self.genes
+            .iter()
+            .zip(&other.genes)
+            .map(|(gene_a, gene_b)| gene_a.distance(gene_b))
+            .sum::<f32>() / self.genes.len() as f32
+    }
     
     
 }
 
-impl Index<usize> for Chromosome {
-    type Output = f32;
+impl<G> Index<usize> for Chromosome<G> {
+    type Output = G;
     
     fn index(&self, index: usize) -> &Self::Output {
         &self.genes[index]
@@ -156,39 +572,53 @@ impl Index<usize> for Chromosome {
 }
 
 
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = impl Iterator<Item = f32>;
+impl<G> IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = impl Iterator<Item = G>;
     
     fn into_iter(self) -> Self::IntoIter {
         self.genes.into_iter()
     }
 }
 
-impl<'a> IntoIterator for &'a Chromosome {
-    type Item = &'a f32;
-    type IntoIter = impl Iterator<Item = &'a f32>;
+impl<'a, G> IntoIterator for &'a Chromosome<G> {
+    type Item = &'a G;
+    type IntoIter = impl Iterator<Item = &'a G>;
     
     fn into_iter(self) -> Self::IntoIter {
         self.genes.iter()
     }
 }
 
-impl FromIterator<f32> for Chromosome {
+impl<G> FromIterator<G> for Chromosome<G> {
     fn from_iter<T>(iter: T) -> Self
     where
-        T: IntoIterator<Item = f32>,
+        T: IntoIterator<Item = G>,
         {
             Self {
                 genes: iter.into_iter().collect(),
+                strategy: vec![],
+                lineage: None,
             }
         }
 }
 
 
 
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+pub trait MutationMethod<G = f32>: MaybeSendSync {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+
+    fn set_rates(&mut self, _chance: f32, _coeff: f32) {}
+}
+
+impl<G> MutationMethod<G> for Box<dyn MutationMethod<G>> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        (**self).mutate(rng, child)
+    }
+
+    fn set_rates(&mut self, chance: f32, coeff: f32) {
+        (**self).set_rates(chance, coeff)
+    }
 }
 
 
@@ -205,75 +635,1125 @@ impl GaussianMutation {
     }
 }
 
+// `usize` ranges draw 64 bits on native and 32 on wasm32, so indices go
+// through `u32` to keep both targets on the same stream.
+pub fn random_index(rng: &mut dyn RngCore, len: usize) -> usize {
+    rng.gen_range(0..len as u32) as usize
+}
+
+// Box-Muller over `libm` rather than `rand_distr`, whose std math may round
+// differently between native and wasm targets.
+pub(crate) fn standard_normal(rng: &mut dyn RngCore) -> f32 {
+    let radius = 1.0 - rng.gen::<f32>();
+    let angle = rng.gen::<f32>();
+    libm::sqrtf(-2.0 * libm::logf(radius)) * libm::cosf(std::f32::consts::TAU * angle)
+}
+
 impl MutationMethod for GaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene += self.coeff * standard_normal(rng);
+            }
+        }
+    }
+
+    fn set_rates(&mut self, chance: f32, coeff: f32) {
+        self.chance = chance.clamp(0.0, 1.0);
+        self.coeff = coeff;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CreepMutation {
+    chance: f32,
+    coeff: f32,
+}
+
+impl CreepMutation {
+    pub fn new(chance: f32, coeff: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        Self { chance, coeff }
+    }
+}
+
+impl MutationMethod for CreepMutation {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
         for gene in child.iter_mut() {
             let sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
-            
+
             if rng.gen_bool(self.chance as _) {
                 *gene += sign * self.coeff * rng.gen::<f32>();
             }
         }
     }
+
+    fn set_rates(&mut self, chance: f32, coeff: f32) {
+        self.chance = chance.clamp(0.0, 1.0);
+        self.coeff = coeff;
+    }
 }
 
+#[derive(Clone, Debug)]
+pub struct UniformResetMutation<G = f32> {
+    chance: f32,
+    min: G,
+    max: G,
+}
 
+impl<G> UniformResetMutation<G> where G: SampleUniform + PartialOrd + Copy {
+    pub fn new(chance: f32, min: G, max: G) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(min < max);
+        Self { chance, min, max }
+    }
+}
+
+impl<G> MutationMethod<G> for UniformResetMutation<G> where G: SampleUniform + PartialOrd + Copy + MaybeSendSync {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = rng.gen_range(self.min..=self.max);
+            }
+        }
+    }
+
+    fn set_rates(&mut self, chance: f32, _coeff: f32) {
+        self.chance = chance.clamp(0.0, 1.0);
+    }
+}
 
-#[cfg(test)]
 #[derive(Clone, Debug)]
-pub struct MockIndividual {
-    fitness: f32,
+pub struct PolynomialMutation {
+    chance: f32,
+    eta: f32,
+    min: f32,
+    max: f32,
 }
 
-#[cfg(test)]
-impl MockIndividual {
-    pub fn new(fitness: f32) -> Self {
-        Self { fitness }
+impl PolynomialMutation {
+    pub fn new(chance: f32, eta: f32, min: f32, max: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(eta >= 0.0);
+        assert!(min < max);
+        Self { chance, eta, min, max }
     }
 }
 
-#[cfg(test)]
-impl Individual for MockIndividual {
-    fn fitness(&self) -> f32 {
-        self.fitness
+impl MutationMethod for PolynomialMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                let u = rng.gen::<f32>();
+                let delta = if u < 0.5 {
+                    libm::powf(2.0 * u, exponent) - 1.0
+                } else {
+                    1.0 - libm::powf(2.0 * (1.0 - u), exponent)
+                };
+
+                *gene = (*gene + delta * (self.max - self.min)).clamp(self.min, self.max);
+            }
+        }
+    }
+
+    fn set_rates(&mut self, chance: f32, _coeff: f32) {
+        self.chance = chance.clamp(0.0, 1.0);
     }
 }
 
 
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[derive(Clone, Debug)]
+pub struct BitFlipMutation {
+    chance: f32,
+}
 
-    #[test]
-    fn it_works() {
-        let mut rng = ChaCha8Rng::from_seed(Default::default());
-        let method = RouletteWheelSelection::new();
-        let population = vec![
-            MockIndividual::new(1.0),
-            MockIndividual::new(2.0),
-            MockIndividual::new(3.0),
-            MockIndividual::new(4.0),
-            MockIndividual::new(5.0),
-        ];
-        
-       let expected_histogram = maplit::btreemap! {
-        1 => 72,
-        2 => 130,
-        3 => 202,
-        4 => 278,
-        5 => 318,
-       };
-       
-       let actual_histogram: BTreeMap<i32, _> = (0..1000)
-        .map(|_| method.select(&mut rng, &population))
-        .fold(Default::default(), |mut histogram, individual| {
-            *histogram.entry(individual.fitness() as _).or_default() += 1;
-            histogram
-        });
-        
-        assert_eq!(actual_histogram, expected_histogram);
-        
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        Self { chance }
     }
 }
 
+impl MutationMethod<bool> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<bool>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = !*gene;
+            }
+        }
+    }
+
+    fn set_rates(&mut self, chance: f32, _coeff: f32) {
+        self.chance = chance.clamp(0.0, 1.0);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AlleleMutation<G> {
+    chance: f32,
+    alleles: Vec<G>,
+}
+
+impl<G> AlleleMutation<G> where G: Clone {
+    pub fn new(chance: f32, alleles: Vec<G>) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(!alleles.is_empty());
+        Self { chance, alleles }
+    }
+}
+
+impl<G> MutationMethod<G> for AlleleMutation<G> where G: Clone + MaybeSendSync {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = self.alleles.choose(rng).expect("alleles must not be empty").clone();
+            }
+        }
+    }
+
+    fn set_rates(&mut self, chance: f32, _coeff: f32) {
+        self.chance = chance.clamp(0.0, 1.0);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SelfAdaptiveMutation {
+    initial_sigma: f32,
+    min_sigma: f32,
+    per_gene: bool,
+}
+
+impl SelfAdaptiveMutation {
+    pub fn global(initial_sigma: f32, min_sigma: f32) -> Self {
+        assert!(initial_sigma > 0.0);
+        assert!(min_sigma > 0.0);
+        Self { initial_sigma, min_sigma, per_gene: false }
+    }
+
+    pub fn per_gene(initial_sigma: f32, min_sigma: f32) -> Self {
+        assert!(initial_sigma > 0.0);
+        assert!(min_sigma > 0.0);
+        Self { initial_sigma, min_sigma, per_gene: true }
+    }
+}
+
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let genes = child.genes.len();
+        if genes == 0 {
+            return;
+        }
+
+        let sigmas = if self.per_gene { genes } else { 1 };
+        if child.strategy.len() != sigmas {
+            child.strategy = vec![self.initial_sigma; sigmas];
+        }
+
+        let n = genes as f32;
+        let common = standard_normal(rng);
+
+        if self.per_gene {
+            let tau_prime = 1.0 / (2.0 * n).sqrt();
+            let tau = 1.0 / (2.0 * n.sqrt()).sqrt();
+
+            for sigma in child.strategy.iter_mut() {
+                let own = standard_normal(rng);
+                *sigma = (*sigma * libm::expf(tau_prime * common + tau * own)).max(self.min_sigma);
+            }
+        } else {
+            let tau = 1.0 / n.sqrt();
+            child.strategy[0] = (child.strategy[0] * libm::expf(tau * common)).max(self.min_sigma);
+        }
+
+        for (index, gene) in child.genes.iter_mut().enumerate() {
+            let sigma = child.strategy[if self.per_gene { index } else { 0 }];
+            *gene += sigma * standard_normal(rng);
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Clone, Debug)]
+pub struct MockIndividual<G = f32> {
+    fitness: f32,
+    chromosome: Chromosome<G>,
+}
+
+#[cfg(test)]
+impl MockIndividual {
+    pub fn new(fitness: f32) -> Self {
+        Self { fitness, chromosome: Chromosome::new(vec![]) }
+    }
+}
+
+#[cfg(test)]
+impl<G> Individual for MockIndividual<G> where G: Gene {
+    type Gene = G;
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        &self.chromosome
+    }
+
+    fn create(chromosome: Chromosome<G>) -> Self {
+        Self { fitness: 0.0, chromosome }
+    }
+}
+
+
+
+#[cfg(test)]
+impl MultiObjective for MockIndividual {
+    fn objectives(&self) -> Vec<f32> {
+        self.chromosome.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+impl Behaviour for MockIndividual {
+    fn behaviour(&self) -> Vec<f32> {
+        self.chromosome.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Four individuals with fitness 1 to 4, each with the chromosome built for its fitness.
+    fn ranked_population<G>(mut chromosome: impl FnMut(usize) -> Chromosome<G>) -> Vec<MockIndividual<G>> {
+        (1..=4)
+            .map(|fitness| MockIndividual {
+                fitness: fitness as f32,
+                chromosome: chromosome(fitness),
+            })
+            .collect()
+    }
+
+    // Fitness peaks at 1.0 when every gene is 1.0.
+    fn evaluate_sphere(population: &mut [MockIndividual]) {
+        for individual in population.iter_mut() {
+            let error: f32 = individual.chromosome.iter().map(|gene| (gene - 1.0).powi(2)).sum();
+            individual.fitness = 1.0 / (1.0 + error);
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let method = RouletteWheelSelection::new();
+        let population = vec![
+            MockIndividual::new(1.0),
+            MockIndividual::new(2.0),
+            MockIndividual::new(3.0),
+            MockIndividual::new(4.0),
+            MockIndividual::new(5.0),
+        ];
+        
+       let expected_histogram = maplit::btreemap! {
+        1 => 72,
+        2 => 130,
+        3 => 202,
+        4 => 278,
+        5 => 318,
+       };
+       
+       let actual_histogram: BTreeMap<i32, _> = (0..1000)
+        .map(|_| method.select(&mut rng, &population))
+        .fold(Default::default(), |mut histogram, individual| {
+            *histogram.entry(individual.fitness() as _).or_default() += 1;
+            histogram
+        });
+        
+        assert_eq!(actual_histogram, expected_histogram);
+        
+    }
+
+    #[test]
+    fn neuron_crossover_keeps_neurons_intact() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let method = NeuronCrossover::new(vec![2, 3, 1]);
+        let parent_a: Chromosome = (0..6).map(|gene| gene as f32).collect();
+        let parent_b: Chromosome = (0..6).map(|gene| -(gene as f32) - 1.0).collect();
+
+        for _ in 0..100 {
+            let child: Vec<f32> = method.crossover(&mut rng, &parent_a, &parent_b).into_iter().collect();
+            let from_a: Vec<bool> = child.iter().map(|gene| *gene >= 0.0).collect();
+
+            assert_eq!(from_a[0], from_a[1]);
+            assert_eq!(from_a[2], from_a[3]);
+            assert_eq!(from_a[3], from_a[4]);
+        }
+    }
+
+    #[test]
+    fn mutations_respect_zero_chance() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let methods: Vec<Box<dyn MutationMethod>> = vec![
+            Box::new(GaussianMutation::new(0.0, 1.0)),
+            Box::new(CreepMutation::new(0.0, 1.0)),
+            Box::new(UniformResetMutation::new(0.0, -1.0, 1.0)),
+            Box::new(PolynomialMutation::new(0.0, 20.0, -1.0, 1.0)),
+        ];
+
+        for method in methods {
+            let mut child = Chromosome::new(vec![0.5, -0.5, 0.25]);
+            method.mutate(&mut rng, &mut child);
+            assert_eq!(child.into_iter().collect::<Vec<_>>(), vec![0.5, -0.5, 0.25]);
+        }
+    }
+
+    #[test]
+    fn polynomial_mutation_stays_in_bounds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let method = PolynomialMutation::new(1.0, 1.0, -1.0, 1.0);
+        let mut child = Chromosome::new(vec![0.9; 100]);
+
+        for _ in 0..10 {
+            method.mutate(&mut rng, &mut child);
+        }
+
+        assert!(child.iter().all(|gene| (-1.0..=1.0).contains(gene)));
+    }
+
+    #[test]
+    fn schedule_drives_mutation_rates() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_mutation_schedule(DecaySchedule::new(1.0, 1.0, 0.0));
+
+        let population = ranked_population(|_| Chromosome::new(vec![1.0, 2.0]));
+
+        let first = ga.evolve(&mut rng, &population);
+        assert!(first.iter().all(|individual| individual.chromosome().iter().ne([1.0, 2.0].iter())));
+
+        let second = ga.evolve(&mut rng, &population);
+        assert!(second.iter().all(|individual| individual.chromosome().iter().eq([1.0, 2.0].iter())));
+        assert_eq!(ga.generation(), 2);
+    }
+
+    #[test]
+    fn self_adaptive_mutation_carries_strategy() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let method = SelfAdaptiveMutation::per_gene(0.5, 0.01);
+        let mut child = Chromosome::new(vec![0.0; 4]);
+
+        method.mutate(&mut rng, &mut child);
+
+        assert_eq!(child.strategy().len(), 4);
+        assert!(child.strategy().iter().all(|sigma| *sigma >= 0.01 && *sigma != 0.5));
+        assert_eq!(child.clone().into_iter().count(), 4);
+
+        let other = Chromosome::new(vec![1.0; 4]).with_strategy(vec![2.0; 4]);
+        let offspring = UniformCrossover::new().crossover(&mut rng, &child, &other);
+
+        for (gene, sigma) in offspring.iter().zip(offspring.strategy()) {
+            assert_eq!(*gene == 1.0, *sigma == 2.0);
+        }
+
+        // Per-gene steps vary by τ = 1/√(2√n) around the shared one.
+        let mut child = Chromosome::new(vec![0.0; 200]);
+        method.mutate(&mut rng, &mut child);
+
+        let logs: Vec<f32> = child.strategy().iter().map(|sigma| sigma.ln()).collect();
+        let mean = logs.iter().sum::<f32>() / logs.len() as f32;
+        let spread = (logs.iter().map(|log| (log - mean).powi(2)).sum::<f32>() / logs.len() as f32).sqrt();
+        assert!((0.15..0.23).contains(&spread), "spread {}", spread);
+    }
+
+    #[test]
+    fn boundary_policies() {
+        assert_eq!(BoundaryPolicy::Clamp.apply(1.5, -1.0, 1.0), 1.0);
+        assert_eq!(BoundaryPolicy::Reflect.apply(1.5, -1.0, 1.0), 0.5);
+        assert_eq!(BoundaryPolicy::Reflect.apply(-3.5, -1.0, 1.0), 0.5);
+        assert_eq!(BoundaryPolicy::Wrap.apply(1.5, -1.0, 1.0), -0.5);
+        assert_eq!(BoundaryPolicy::Wrap.apply(0.25, -1.0, 1.0), 0.25);
+    }
+
+    #[test]
+    fn evolve_applies_bounds_and_repair() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 10.0),
+        )
+        .with_bounds(GeneBounds::per_gene(vec![(0.0, 1.0), (-1.0, 0.0)], BoundaryPolicy::Reflect))
+        .with_repair(|chromosome: &mut Chromosome| {
+            for gene in chromosome.iter_mut() {
+                *gene = (*gene * 10.0).round() / 10.0;
+            }
+        });
+
+        let population = ranked_population(|_| Chromosome::new(vec![0.5, -0.5]));
+
+        for individual in ga.evolve(&mut rng, &population) {
+            assert!((0.0..=1.0).contains(&individual.chromosome()[0]));
+            assert!((-1.0..=0.0).contains(&individual.chromosome()[1]));
+        }
+    }
+
+    #[test]
+    fn evolves_bool_chromosomes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            BitFlipMutation::new(1.0),
+        );
+
+        let population = ranked_population(|_| Chromosome::new(vec![true, false, true]));
+
+        for individual in ga.evolve(&mut rng, &population) {
+            assert_eq!(individual.chromosome().iter().copied().collect::<Vec<_>>(), vec![false, true, false]);
+        }
+    }
+
+    #[test]
+    fn evolves_enum_chromosomes() {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        enum Cells {
+            Few,
+            Many,
+        }
+
+        impl Gene for Cells {
+            fn distance(&self, other: &Self) -> f32 {
+                if self == other { 0.0 } else { 1.0 }
+            }
+
+            fn value(&self) -> f32 {
+                *self as u8 as f32
+            }
+        }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            AlleleMutation::new(1.0, vec![Cells::Many]),
+        );
+
+        let population = ranked_population(|_| Chromosome::new(vec![Cells::Few; 3]));
+
+        for individual in ga.evolve(&mut rng, &population) {
+            assert!(individual.chromosome().iter().all(|cells| *cells == Cells::Many));
+        }
+
+        let reset = UniformResetMutation::new(1.0, 3_i32, 5);
+        let mut child = Chromosome::new(vec![0_i32; 10]);
+        reset.mutate(&mut rng, &mut child);
+        assert!(child.iter().all(|gene| (3..=5).contains(gene)));
+    }
+
+    #[test]
+    fn speciation_partitions_and_allocates() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_speciation(Speciation::new(1.0));
+
+        let population: Vec<_> = [(0.0, 1.0), (0.1, 1.0), (5.0, 1.0), (5.1, 1.0)]
+            .into_iter()
+            .map(|(gene, fitness)| MockIndividual {
+                fitness,
+                chromosome: Chromosome::new(vec![gene, gene]),
+            })
+            .collect();
+
+        let offspring = ga.evolve(&mut rng, &population);
+
+        assert_eq!(ga.species(), &[0, 0, 1, 1]);
+        assert_eq!(offspring.iter().filter(|individual| individual.chromosome()[0] < 1.0).count(), 2);
+        assert_eq!(offspring.iter().filter(|individual| individual.chromosome()[0] > 4.0).count(), 2);
+
+        let species = [vec![0], vec![1, 2]];
+        let shared = share_fitness(&species, &[-1.0, 2.0, 2.0]);
+        assert_eq!(allocate_offspring(&species, &shared, 3), [0, 3]);
+    }
+
+    #[test]
+    fn island_model_migrates_best_on_ring() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let islands = (0..3)
+            .map(|_| GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.0, 0.0),
+            ))
+            .collect();
+        let mut model = IslandModel::new(islands, MigrationTopology::Ring, 2, 1);
+
+        let populations: Vec<Vec<_>> = (0..3)
+            .map(|island| {
+                ranked_population(|fitness| {
+                    Chromosome::new(vec![island as f32 * 10.0 + fitness as f32]).with_lineage(model.founder(island))
+                })
+            })
+            .collect();
+
+        let first = model.evolve(&mut rng, &populations);
+        for (island, population) in first.iter().enumerate() {
+            assert!(population.iter().all(|individual| (individual.chromosome()[0] / 10.0) as usize == island));
+        }
+
+        let second = model.evolve(&mut rng, &populations);
+        for (island, population) in second.iter().enumerate() {
+            let source = (island + 2) % 3;
+            let migrant = source as f32 * 10.0 + 4.0;
+            assert!(population.iter().any(|individual| individual.chromosome()[0] == migrant));
+        }
+        assert_eq!(model.generation(), 2);
+
+        // Founders, children and re-stamped migrants never share an id.
+        let mut ids: Vec<u64> = [&populations, &first, &second]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|individual| individual.chromosome.lineage().unwrap().id())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 36);
+    }
+
+    #[test]
+    fn non_dominated_sort_and_crowding() {
+        let objectives = vec![
+            vec![1.0, 5.0],
+            vec![2.0, 4.0],
+            vec![3.0, 1.0],
+            vec![1.0, 1.0],
+            vec![0.5, 0.5],
+        ];
+
+        let fronts = non_dominated_sort(&objectives);
+        assert_eq!(fronts, vec![vec![0, 1, 2], vec![3], vec![4]]);
+
+        let distance = crowding_distance(&objectives, &fronts[0]);
+        assert!(distance[0].is_infinite());
+        assert!(distance[2].is_infinite());
+        assert!((distance[1] - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn evolve_multi_objective_keeps_pareto_front() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = GeneticAlgorithm::new(
+            Nsga2Selection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_hall_of_fame(2);
+
+        let population: Vec<_> = [[1.0, 5.0], [5.0, 1.0], [0.0, 0.0], [0.5, 0.5]]
+            .into_iter()
+            .map(|genes| MockIndividual {
+                fitness: 0.0,
+                chromosome: Chromosome::new(genes.to_vec()),
+            })
+            .collect();
+
+        let offspring = ga.evolve_multi_objective(&mut rng, &population);
+        assert_eq!(offspring.len(), 4);
+        assert_eq!(ga.hall_of_fame().members().len(), 2);
+
+        let mut front: Vec<Vec<f32>> = ga.pareto_front().map(|(_, objectives)| objectives.to_vec()).collect();
+        front.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        assert_eq!(front, vec![vec![1.0, 5.0], vec![5.0, 1.0]]);
+
+        // Neither the configured selection method nor scalar transforms touch
+        // the crowded-comparison scores.
+        let genes = |mut ga: GeneticAlgorithm<Box<dyn SelectionMethod>>| -> Vec<Vec<f32>> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let offspring: Vec<MockIndividual> = ga.evolve_multi_objective(&mut rng, &population);
+            offspring.iter().map(|individual| individual.chromosome().iter().copied().collect()).collect()
+        };
+        let roulette = GeneticAlgorithm::new(
+            Box::new(RouletteWheelSelection::new()) as Box<dyn SelectionMethod>,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_fitness_transform(LinearScaling::new(2.0));
+        let nsga2 = GeneticAlgorithm::new(
+            Box::new(Nsga2Selection::new()) as Box<dyn SelectionMethod>,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        );
+        assert_eq!(genes(roulette), genes(nsga2));
+    }
+
+    #[test]
+    fn novelty_search_rewards_distinct_behaviour() {
+        let mut search = NoveltySearch::new(2, 1.0, 1.0);
+        let population: Vec<_> = [[0.0, 0.0], [0.1, 0.0], [0.0, 0.1], [5.0, 5.0]]
+            .into_iter()
+            .map(|genes| MockIndividual {
+                fitness: 1.0,
+                chromosome: Chromosome::new(genes.to_vec()),
+            })
+            .collect();
+
+        let scores = search.score(&population);
+
+        assert_eq!(scores[3], 1.0);
+        assert!(scores[..3].iter().all(|score| *score < 0.1));
+        assert_eq!(search.archive(), &[vec![5.0, 5.0]]);
+    }
+
+    #[test]
+    fn steady_state_replaces_worst_and_oldest() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_mutation_schedule(AdaptiveSchedule::new(0.0, 0.0, 0.1, 1.0));
+
+        let mut population: Vec<_> = [3.0, 1.0, 2.0]
+            .into_iter()
+            .map(|fitness| MockIndividual {
+                fitness,
+                chromosome: Chromosome::new(vec![fitness]),
+            })
+            .collect();
+
+        assert_eq!(ga.steady_state(&mut rng, &mut population, Replacement::Worst), 1);
+        assert_eq!(population[1].fitness(), 0.0);
+        let diversity = ga.diversity().clone();
+
+        assert_eq!(ga.steady_state(&mut rng, &mut population, Replacement::Oldest), 0);
+        assert_eq!(ga.diversity(), &diversity);
+        assert_eq!(ga.steady_state(&mut rng, &mut population, Replacement::Oldest), 2);
+        assert_eq!(ga.generation(), 1);
+
+        assert_eq!(ga.steady_state(&mut rng, &mut population, Replacement::Oldest), 1);
+        assert_ne!(ga.diversity(), &diversity);
+    }
+
+    #[test]
+    fn runner_stops_on_termination_and_notifies_observers() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let seen = Rc::new(RefCell::new(vec![]));
+        let observed = seen.clone();
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.2, 0.5),
+        );
+        let mut runner = Runner::new(ga)
+            .with_termination(Termination::MaxGenerations(50))
+            .with_termination(Termination::FitnessTarget(0.95))
+            .with_observer(move |statistics| observed.borrow_mut().push(statistics.generation));
+
+        let population: Vec<_> = (0..20)
+            .map(|_| MockIndividual::create(Chromosome::new(vec![0.0; 4])))
+            .collect();
+
+        let outcome = runner.run(&mut rng, population, |population| {
+            for individual in population {
+                let error: f32 = individual.chromosome.iter().map(|gene| (gene - 1.0).powi(2)).sum();
+                individual.fitness = 1.0 / (1.0 + error);
+            }
+        });
+
+        let seen = seen.borrow();
+        assert_eq!(seen.len(), outcome.statistics.generation + 1);
+        assert!(seen.windows(2).all(|pair| pair[1] == pair[0] + 1));
+        match outcome.termination {
+            Termination::FitnessTarget(_) => assert!(outcome.statistics.max_fitness >= 0.95),
+            Termination::MaxGenerations(_) => assert_eq!(outcome.statistics.generation, 49),
+            termination => panic!("unexpected termination: {:?}", termination),
+        }
+    }
+
+    #[test]
+    fn checkpoint_restores_bit_identical_continuation() {
+        let population: Vec<_> = (1..=8)
+            .map(|fitness| MockIndividual {
+                fitness: fitness as f32,
+                chromosome: Chromosome::new(vec![fitness as f32 / 3.0; 4]),
+            })
+            .collect();
+
+        let build = || {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_hall_of_fame(3)
+        };
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = build();
+        let population = ga.evolve(&mut rng, &population);
+        let checkpoint = ga.checkpoint(&population, &rng);
+
+        let expected: Vec<Vec<f32>> = ga
+            .evolve(&mut rng, &population)
+            .iter()
+            .map(|individual| individual.chromosome().iter().copied().collect())
+            .collect();
+
+        let mut restored = build();
+        restored.restore(&checkpoint);
+        let mut rng = checkpoint.rng.clone();
+        let population: Vec<MockIndividual> = checkpoint.population
+            .iter()
+            .map(|(chromosome, fitness)| MockIndividual { fitness: *fitness, chromosome: chromosome.clone() })
+            .collect();
+
+        let actual: Vec<Vec<f32>> = restored
+            .evolve(&mut rng, &population)
+            .iter()
+            .map(|individual| individual.chromosome().iter().copied().collect())
+            .collect();
+
+        assert_eq!(actual, expected);
+        assert_eq!(restored.generation(), 2);
+        assert_eq!(restored.hall_of_fame().members().len(), 3);
+        assert_eq!(restored.hall_of_fame().members()[0].1, 8.0);
+    }
+
+    #[test]
+    fn optimizers_share_a_trait() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let optimizers: Vec<Box<dyn Optimizer<MockIndividual>>> = vec![
+            Box::new(GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.1, 0.1),
+            )),
+            Box::new(CmaEs::new(0.5)),
+        ];
+
+        let mut best_per_optimizer = vec![];
+        for mut optimizer in optimizers {
+            let mut population: Vec<_> = (0..16)
+                .map(|_| MockIndividual::create((0..5).map(|_| rng.gen_range(-1.0..1.0)).collect()))
+                .collect();
+            evaluate_sphere(&mut population);
+            let initial = population.iter().map(|individual| individual.fitness).fold(0.0, f32::max);
+
+            for _ in 0..150 {
+                evaluate_sphere(&mut population);
+                population = optimizer.evolve(&mut rng, &population);
+            }
+
+            evaluate_sphere(&mut population);
+            assert_eq!(optimizer.generation(), 150);
+            let best = population.iter().map(|individual| individual.fitness).fold(0.0, f32::max);
+            assert!(best > initial, "{} did not improve on {}", best, initial);
+            best_per_optimizer.push(best);
+        }
+
+        assert!(best_per_optimizer[1] > 0.99, "CMA-ES did not converge: {}", best_per_optimizer[1]);
+    }
+
+    #[test]
+    fn differential_evolution_strategies() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        for strategy in [
+            DifferentialStrategy::Rand1,
+            DifferentialStrategy::Best1,
+            DifferentialStrategy::CurrentToBest1,
+        ] {
+            let mut optimizer = DifferentialEvolution::new(DifferentialMutation::new(strategy, 0.5, 0.9));
+            let mut population: Vec<_> = (0..20)
+                .map(|_| MockIndividual::create((0..5).map(|_| rng.gen_range(-1.0..1.0)).collect()))
+                .collect();
+
+            evaluate_sphere(&mut population);
+            let initial = population.iter().map(|individual| individual.fitness).fold(0.0, f32::max);
+
+            for _ in 0..150 {
+                population = optimizer.evolve(&mut rng, &population);
+                evaluate_sphere(&mut population);
+            }
+
+            let best = population.iter().map(|individual| individual.fitness).fold(0.0, f32::max);
+            assert!(best > initial.max(0.75), "{:?} did not improve: {} -> {}", strategy, initial, best);
+        }
+    }
+
+    #[test]
+    fn fitness_transforms() {
+        let fitness = [2.0, 4.0, 4.0, 10.0];
+
+        assert_eq!(Windowing::new().transform(&fitness), vec![0.0, 2.0, 2.0, 8.0]);
+        assert_eq!(RankNormalization::new().transform(&fitness), vec![0.25, 0.625, 0.625, 1.0]);
+        assert_eq!(PowerLaw::new(2.0).transform(&fitness), vec![4.0, 16.0, 16.0, 100.0]);
+
+        let scaled = LinearScaling::new(2.0).transform(&fitness);
+        assert!((scaled.iter().sum::<f32>() / 4.0 - 5.0).abs() < 1e-5);
+        assert!((scaled[3] - 10.0).abs() < 1e-5);
+
+        let truncated = SigmaTruncation::new(1.0).transform(&fitness);
+        assert_eq!(truncated[0], 0.0);
+        assert!(truncated.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_fitness_transform(Windowing::new())
+        .with_fitness_transform(|fitness: &[f32]| fitness.iter().map(|value| value * 2.0).collect::<Vec<f32>>());
+
+        let transformed = ga
+            .fitness_transforms
+            .iter()
+            .fold(fitness.to_vec(), |fitness, transform| transform.transform(&fitness));
+        assert_eq!(transformed, vec![0.0, 4.0, 4.0, 16.0]);
+    }
+
+    #[test]
+    fn offspring_carry_lineage() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let population = ranked_population(|fitness| Chromosome::new(vec![fitness as f32; 3]).with_lineage(ga.founder()));
+
+        let offspring: Vec<MockIndividual> = ga.evolve(&mut rng, &population);
+        let ids: Vec<u64> = offspring.iter().map(|individual| individual.chromosome.lineage().unwrap().id()).collect();
+        assert_eq!(ids, vec![4, 5, 6, 7]);
+
+        for individual in &offspring {
+            let parents = individual.chromosome.lineage().unwrap().parents();
+            assert!(!parents.is_empty() && parents.iter().all(|&id| id < 4));
+        }
+
+        let grandchild: MockIndividual = ga.offspring(&mut rng, &offspring);
+        let lineage = grandchild.chromosome.lineage().unwrap();
+        assert_eq!(lineage.id(), 8);
+        assert!(lineage.parents().iter().all(|id| ids.contains(id)));
+    }
+
+    #[test]
+    fn diversity_metrics() {
+        let population: Vec<_> = [[0.0, 0.0], [0.0, 0.0], [1.0, 2.0], [1.0, 4.0]]
+            .iter()
+            .map(|genes| MockIndividual {
+                fitness: 1.0,
+                chromosome: Chromosome::new(genes.to_vec()),
+            })
+            .collect();
+
+        let diversity = Diversity::with_bins(&population, 3);
+        assert_eq!(diversity.unique_genotypes, 3);
+        assert_eq!(diversity.gene_variance, vec![0.25, 2.75]);
+        assert!((diversity.entropy - 0.7887).abs() < 1e-3);
+        assert!((diversity.mean_distance - 1.5).abs() < 1e-6);
+        assert!((diversity.mean_deviation() - 1.0792).abs() < 1e-3);
+        assert!(!diversity.is_collapsed(0.1));
+
+        let clones: Vec<_> = (0..4)
+            .map(|_| MockIndividual {
+                fitness: 1.0,
+                chromosome: Chromosome::new(vec![0.5, 0.5]),
+            })
+            .collect();
+
+        let diversity = Diversity::new(&clones);
+        assert_eq!(diversity.unique_genotypes, 1);
+        assert_eq!(diversity.entropy, 0.0);
+        assert!(diversity.is_collapsed(0.1));
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let ga = || GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        );
+
+        let mut unscheduled = ga();
+        unscheduled.evolve(&mut rng, &population);
+        assert_eq!(unscheduled.diversity(), &Diversity::default());
+
+        let mut scheduled = ga().with_mutation_schedule(AdaptiveSchedule::new(0.0, 0.0, 0.1, 1.0));
+        scheduled.evolve(&mut rng, &population);
+        assert_eq!(scheduled.diversity(), &Diversity::new(&population));
+
+        scheduled.observe_diversity(diversity.clone());
+        scheduled.evolve(&mut rng, &population);
+        assert_eq!(scheduled.diversity(), &diversity);
+    }
+
+    #[test]
+    fn builds_from_config() {
+        let config = GaConfig::from_toml(r#"
+            hall_of_fame = 2
+            speciation_threshold = 0.5
+
+            [selection]
+            type = "tournament"
+            size = 3
+
+            [crossover]
+            type = "neuron"
+            neurons = [2, 2]
+
+            [mutation]
+            type = "polynomial"
+            chance = 0.2
+            eta = 20.0
+            min = -1.0
+            max = 1.0
+
+            [schedule]
+            type = "decay"
+            chance = 0.2
+            coeff = 0.1
+            decay = 0.9
+
+            [[transforms]]
+            type = "rank_normalization"
+
+            [bounds]
+            min = -1.0
+            max = 1.0
+            policy = "reflect"
+        "#).unwrap();
+
+        assert_eq!(config.selection, SelectionConfig::Tournament { size: 3 });
+        assert_eq!(config, GaConfig::from_json(&serde_json::to_string(&config).unwrap()).unwrap());
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = config.build().unwrap();
+        let population = ranked_population(|fitness| Chromosome::new(vec![fitness as f32 / 4.0; 4]));
+
+        let offspring: Vec<MockIndividual> = ga.evolve(&mut rng, &population);
+        assert_eq!(offspring.len(), 4);
+        assert!(offspring.iter().flat_map(|individual| individual.chromosome.iter()).all(|gene| (-1.0..=1.0).contains(gene)));
+        assert_eq!(ga.hall_of_fame().members().len(), 2);
+
+        let invalid = GaConfig::from_json(r#"{"mutation": {"type": "gaussian", "chance": 1.5, "coeff": 0.1}}"#);
+        assert_eq!(
+            invalid.unwrap_err(),
+            ConfigError::Invalid { field: "mutation.chance", reason: "must be between 0 and 1".into() },
+        );
+        assert!(matches!(GaConfig::from_toml("[mutation]\ntype = \"laser\""), Err(ConfigError::Parse(_))));
+
+        let invalid = GaConfig::from_toml("[selection]\ntype = \"tournament\"\nsize = 0");
+        assert!(matches!(invalid, Err(ConfigError::Invalid { field: "selection.size", .. })));
+
+        let invalid = GaConfig::from_toml("[crossover]\ntype = \"neuron\"\nneurons = []");
+        assert!(matches!(invalid, Err(ConfigError::Invalid { field: "crossover.neurons", .. })));
+
+        let invalid = GaConfig::from_json(r#"{"schedule": {"type": "decay", "chance": 0.1, "coeff": -0.5, "decay": 0.9}}"#);
+        assert!(matches!(invalid, Err(ConfigError::Invalid { field: "schedule.coeff", .. })));
+    }
+
+    #[test]
+    fn selection_is_chosen_at_runtime() {
+        let population = ranked_population(|fitness| Chromosome::new(vec![fitness as f32]));
+
+        let methods: Vec<Box<dyn SelectionMethod>> = vec![
+            Box::new(RouletteWheelSelection::new()),
+            Box::new(TournamentSelection::new(2)),
+            Box::new("tournament".parse::<SelectionConfig>().unwrap()),
+            Box::new(TournamentSelection::new(4)),
+        ];
+
+        let mut ga: GeneticAlgorithm<Box<dyn SelectionMethod>> = GeneticAlgorithm::new(
+            Box::new(RouletteWheelSelection::new()),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        );
+
+        // Without mutation every child carries the single gene of one of its
+        // parents, i.e. that parent's fitness.
+        let histograms: Vec<BTreeMap<i32, usize>> = methods
+            .into_iter()
+            .map(|method| {
+                ga.set_selection_method(method);
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+                (0..250)
+                    .flat_map(|_| ga.evolve::<MockIndividual>(&mut rng, &population))
+                    .fold(BTreeMap::new(), |mut histogram, child| {
+                        *histogram.entry(child.chromosome[0] as _).or_default() += 1;
+                        histogram
+                    })
+            })
+            .collect();
+
+        assert_ne!(histograms[0], histograms[1]);
+        assert_eq!(histograms[1], histograms[2]);
+        assert!(histograms[0][&1] > histograms[1][&1]);
+        assert!(histograms[3][&4] > histograms[1][&4]);
+
+        assert!("nsga2".parse::<SelectionConfig>().is_err());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn evolve_parallel_ignores_thread_count() {
+        let population: Vec<_> = (1..=64)
+            .map(|fitness| MockIndividual {
+                fitness: fitness as f32,
+                chromosome: Chromosome::new(vec![fitness as f32; 8]),
+            })
+            .collect();
+
+        let run = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            pool.install(|| {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let mut ga = GeneticAlgorithm::new(
+                    RouletteWheelSelection::new(),
+                    UniformCrossover::new(),
+                    GaussianMutation::new(0.5, 0.5),
+                );
+                ga.evolve_parallel(&mut rng, &population)
+                    .into_iter()
+                    .map(|individual| individual.chromosome().iter().copied().collect::<Vec<_>>())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(run(1), run(4));
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    #[test]
+    fn operators_need_not_be_thread_safe() {
+        use std::rc::Rc;
+
+        let multiple = Rc::new(2.0);
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_fitness_transform(move |fitness: &[f32]| fitness.iter().map(|value| value * *multiple).collect());
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = ranked_population(|fitness| Chromosome::new(vec![fitness as f32]));
+        let offspring: Vec<MockIndividual> = ga.evolve(&mut rng, &population);
+        assert_eq!(offspring.len(), 4);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lineage {
+    id: u64,
+    parents: Vec<u64>,
+}
+
+impl Lineage {
+    pub fn new(id: u64, parents: Vec<u64>) -> Self {
+        Self { id, parents }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn parents(&self) -> &[u64] {
+        &self.parents
+    }
+
+    pub(crate) fn descend<G>(id: u64, parent_a: &Chromosome<G>, parent_b: &Chromosome<G>) -> Self {
+        let mut parents: Vec<u64> = [parent_a, parent_b]
+            .iter()
+            .filter_map(|parent| parent.lineage().map(Lineage::id))
+            .collect();
+        parents.dedup();
+
+        Self { id, parents }
+    }
+}
+
+impl<S, G> GeneticAlgorithm<S, G> where S: SelectionMethod, G: Gene {
+    // Lets several algorithms share one genealogy by handing out ids from
+    // disjoint blocks; it has to be set before any id is handed out.
+    pub fn with_id_base(mut self, base: u64) -> Self {
+        assert_eq!(self.next_id, 0, "lineage ids were already handed out");
+        self.next_id = base;
+        self
+    }
+
+    pub fn founder(&mut self) -> Lineage {
+        Lineage::new(self.reserve_ids(1), vec![])
+    }
+
+    pub(crate) fn reserve_ids(&mut self, count: usize) -> u64 {
+        let first = self.next_id;
+        self.next_id += count as u64;
+        first
+    }
+}
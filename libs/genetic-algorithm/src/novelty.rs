@@ -0,0 +1,105 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+pub trait Behaviour {
+    fn behaviour(&self) -> Vec<f32>;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NoveltySearch {
+    neighbours: usize,
+    threshold: f32,
+    weight: f32,
+    archive: Vec<Vec<f32>>,
+}
+
+impl NoveltySearch {
+    pub fn new(neighbours: usize, threshold: f32, weight: f32) -> Self {
+        assert!(neighbours > 0);
+        assert!(threshold >= 0.0);
+        assert!((0.0..=1.0).contains(&weight));
+        Self {
+            neighbours,
+            threshold,
+            weight,
+            archive: vec![],
+        }
+    }
+
+    pub fn archive(&self) -> &[Vec<f32>] {
+        &self.archive
+    }
+
+    pub fn novelty<I>(&self, population: &[I]) -> Vec<f32>
+    where
+        I: Behaviour,
+    {
+        let behaviours: Vec<Vec<f32>> = population.iter().map(Behaviour::behaviour).collect();
+
+        behaviours
+            .iter()
+            .enumerate()
+            .map(|(index, behaviour)| {
+                let mut distances: Vec<f32> = behaviours
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != index)
+                    .map(|(_, other)| other)
+                    .chain(&self.archive)
+                    .map(|other| euclidean(behaviour, other))
+                    .collect();
+
+                distances.sort_by(f32::total_cmp);
+                distances.truncate(self.neighbours);
+
+                if distances.is_empty() {
+                    0.0
+                } else {
+                    distances.iter().sum::<f32>() / distances.len() as f32
+                }
+            })
+            .collect()
+    }
+
+    pub fn score<I>(&mut self, population: &[I]) -> Vec<f32>
+    where
+        I: Individual + Behaviour,
+    {
+        let novelty = self.novelty(population);
+
+        for (individual, novelty) in population.iter().zip(&novelty) {
+            if *novelty > self.threshold {
+                self.archive.push(individual.behaviour());
+            }
+        }
+
+        let fitness: Vec<f32> = population.iter().map(|individual| individual.fitness()).collect();
+        let novelty = normalize(&novelty);
+        let fitness = normalize(&fitness);
+
+        novelty
+            .iter()
+            .zip(&fitness)
+            .map(|(novelty, fitness)| self.weight * novelty + (1.0 - self.weight) * fitness)
+            .collect()
+    }
+}
+
+fn euclidean(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
+
+fn normalize(values: &[f32]) -> Vec<f32> {
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+    if max <= min {
+        return vec![1.0; values.len()];
+    }
+
+    values.iter().map(|value| (value - min) / (max - min)).collect()
+}
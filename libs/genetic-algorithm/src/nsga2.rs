@@ -0,0 +1,164 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+pub trait MultiObjective {
+    fn objectives(&self) -> Vec<f32>;
+}
+
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+pub fn non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let mut dominated_by = vec![0; objectives.len()];
+    let mut dominates_over: Vec<Vec<usize>> = vec![vec![]; objectives.len()];
+
+    for a in 0..objectives.len() {
+        for b in a + 1..objectives.len() {
+            if dominates(&objectives[a], &objectives[b]) {
+                dominates_over[a].push(b);
+                dominated_by[b] += 1;
+            } else if dominates(&objectives[b], &objectives[a]) {
+                dominates_over[b].push(a);
+                dominated_by[a] += 1;
+            }
+        }
+    }
+
+    let mut fronts = vec![];
+    let mut front: Vec<usize> = (0..objectives.len()).filter(|&index| dominated_by[index] == 0).collect();
+
+    while !front.is_empty() {
+        let mut next = vec![];
+
+        for &index in &front {
+            for &other in &dominates_over[index] {
+                dominated_by[other] -= 1;
+                if dominated_by[other] == 0 {
+                    next.push(other);
+                }
+            }
+        }
+
+        fronts.push(front);
+        front = next;
+    }
+
+    fronts
+}
+
+pub fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    let mut distance = vec![0.0; front.len()];
+    let Some(first) = front.first() else {
+        return distance;
+    };
+
+    let columns = (0..objectives[*first].len())
+        .map(|objective| front.iter().map(|&index| objectives[index][objective]).collect::<Vec<f32>>());
+
+    for column in columns {
+        let value = |index: usize| column[index];
+
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+
+        let min = value(order[0]);
+        let max = value(order[order.len() - 1]);
+
+        distance[order[0]] = f32::INFINITY;
+        distance[order[order.len() - 1]] = f32::INFINITY;
+
+        if max <= min {
+            continue;
+        }
+
+        for window in order.windows(3) {
+            let spread = value(window[2]) - value(window[0]);
+            distance[window[1]] += spread / (max - min);
+        }
+    }
+
+    distance
+}
+
+// Binary tournament over the crowded-comparison score produced by
+// `GeneticAlgorithm::evolve_multi_objective`: lower front wins, then larger crowding distance.
+// That method always breeds through it, whatever selection method is configured.
+#[derive(Clone, Debug, Default)]
+pub struct Nsga2Selection;
+
+impl Nsga2Selection {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SelectionMethod for Nsga2Selection {
+    fn select_index(&self, rng: &mut dyn RngCore, fitness: &[f32]) -> usize {
+        assert!(!fitness.is_empty(), "population must not be empty");
+
+        let a = random_index(rng, fitness.len());
+        let b = random_index(rng, fitness.len());
+
+        if fitness[a] >= fitness[b] { a } else { b }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct ParetoArchive<G> {
+    members: Vec<(Chromosome<G>, Vec<f32>)>,
+}
+
+impl<G> ParetoArchive<G> where G: Gene {
+    pub(crate) fn new() -> Self {
+        Self { members: vec![] }
+    }
+
+    pub(crate) fn front(&self) -> impl Iterator<Item = (&Chromosome<G>, &[f32])> {
+        let objectives: Vec<Vec<f32>> = self.members.iter().map(|(_, objectives)| objectives.clone()).collect();
+        let front = non_dominated_sort(&objectives).into_iter().next().unwrap_or_default();
+
+        front
+            .into_iter()
+            .map(|index| (&self.members[index].0, self.members[index].1.as_slice()))
+    }
+
+    pub(crate) fn survive(&mut self, candidates: Vec<(Chromosome<G>, Vec<f32>)>, size: usize) -> Vec<f32> {
+        let mut candidates: Vec<_> = self.members.drain(..).chain(candidates).collect();
+        let objectives: Vec<Vec<f32>> = candidates.iter().map(|(_, objectives)| objectives.clone()).collect();
+        let fronts = non_dominated_sort(&objectives);
+        let count = fronts.len() as f32;
+
+        let mut survivors = vec![];
+        let mut scores = vec![];
+
+        for (rank, front) in fronts.iter().enumerate() {
+            if survivors.len() >= size {
+                break;
+            }
+
+            let distance = crowding_distance(&objectives, front);
+            let mut order: Vec<usize> = (0..front.len()).collect();
+            order.sort_by(|&a, &b| distance[b].total_cmp(&distance[a]));
+            order.truncate(size - survivors.len());
+
+            for index in order {
+                let crowding = if distance[index].is_infinite() { 1.0 } else { distance[index] / (1.0 + distance[index]) };
+                survivors.push(front[index]);
+                scores.push(count - rank as f32 + 0.999 * crowding);
+            }
+        }
+
+        let mut slots: Vec<Option<(Chromosome<G>, Vec<f32>)>> = candidates.drain(..).map(Some).collect();
+        self.members = survivors
+            .into_iter()
+            .map(|index| slots[index].take().expect("survivor selected twice"))
+            .collect();
+
+        scores
+    }
+
+    pub(crate) fn chromosomes(&self) -> impl Iterator<Item = &Chromosome<G>> {
+        self.members.iter().map(|(chromosome, _)| chromosome)
+    }
+}
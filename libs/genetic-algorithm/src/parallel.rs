@@ -0,0 +1,32 @@
+use crate::*;
+use rayon::prelude::*;
+
+impl<S, G> GeneticAlgorithm<S, G>
+where
+    S: SelectionMethod + Sync,
+    G: Gene + Send + Sync,
+{
+    pub fn evolve_parallel<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I>
+    where
+        I: Individual<Gene = G> + Send + Sync,
+    {
+        let seed = rng.next_u64();
+        let fitness = population.iter().map(|individual| individual.fitness()).collect();
+        let (pools, slots) = self.prepare(population, fitness);
+        let parents: Vec<_> = population.iter().map(|individual| individual.chromosome()).collect();
+        let first_id = self.reserve_ids(slots.len());
+
+        let offspring = slots
+            .par_iter()
+            .enumerate()
+            .map(|(slot, &pool)| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                rng.set_stream(slot as u64);
+                self.spawn(&mut rng, &parents, &pools[pool], first_id + slot as u64)
+            })
+            .collect();
+
+        self.generation += 1;
+        offspring
+    }
+}
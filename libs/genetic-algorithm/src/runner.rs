@@ -0,0 +1,129 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Termination {
+    MaxGenerations(usize),
+    FitnessTarget(f32),
+    Stagnation(usize),
+    TimeBudget(Duration),
+    DiversityCollapse(f32),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    pub generation: usize,
+    pub min_fitness: f32,
+    pub max_fitness: f32,
+    pub avg_fitness: f32,
+    pub diversity: Diversity,
+}
+
+impl Statistics {
+    pub fn new<I>(generation: usize, population: &[I]) -> Self
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty());
+
+        let fitness = population.iter().map(|individual| individual.fitness());
+
+        Self {
+            generation,
+            min_fitness: fitness.clone().fold(f32::INFINITY, f32::min),
+            max_fitness: fitness.clone().fold(f32::NEG_INFINITY, f32::max),
+            avg_fitness: fitness.sum::<f32>() / population.len() as f32,
+            diversity: Diversity::new(population),
+        }
+    }
+}
+
+pub struct RunOutcome<I> {
+    pub population: Vec<I>,
+    pub statistics: Statistics,
+    pub termination: Termination,
+}
+
+type Observer = Box<dyn FnMut(&Statistics)>;
+
+pub struct Runner<S, G = f32> {
+    ga: GeneticAlgorithm<S, G>,
+    terminations: Vec<Termination>,
+    observers: Vec<Observer>,
+}
+
+impl<S, G> Runner<S, G> where S: SelectionMethod, G: Gene {
+    pub fn new(ga: GeneticAlgorithm<S, G>) -> Self {
+        Self {
+            ga,
+            terminations: vec![],
+            observers: vec![],
+        }
+    }
+
+    pub fn with_termination(mut self, termination: Termination) -> Self {
+        self.terminations.push(termination);
+        self
+    }
+
+    pub fn with_observer(mut self, observer: impl FnMut(&Statistics) + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    pub fn ga(&self) -> &GeneticAlgorithm<S, G> {
+        &self.ga
+    }
+
+    pub fn run<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        mut population: Vec<I>,
+        mut evaluate: impl FnMut(&mut [I]),
+    ) -> RunOutcome<I>
+    where
+        I: Individual<Gene = G>,
+    {
+        assert!(!self.terminations.is_empty(), "runner needs at least one termination condition");
+
+        let started = Instant::now();
+        let mut best = f32::NEG_INFINITY;
+        let mut stagnant = 0;
+
+        loop {
+            evaluate(&mut population);
+
+            let statistics = Statistics::new(self.ga.generation(), &population);
+            for observer in &mut self.observers {
+                observer(&statistics);
+            }
+
+            if statistics.max_fitness > best {
+                best = statistics.max_fitness;
+                stagnant = 0;
+            } else {
+                stagnant += 1;
+            }
+
+            let reached = self.terminations.iter().find(|termination| match termination {
+                Termination::MaxGenerations(generations) => statistics.generation + 1 >= *generations,
+                Termination::FitnessTarget(target) => statistics.max_fitness >= *target,
+                Termination::Stagnation(generations) => stagnant >= *generations,
+                Termination::TimeBudget(budget) => started.elapsed() >= *budget,
+                Termination::DiversityCollapse(threshold) => statistics.diversity.is_collapsed(*threshold),
+            });
+
+            if let Some(&termination) = reached {
+                return RunOutcome {
+                    population,
+                    statistics,
+                    termination,
+                };
+            }
+
+            self.ga.observe_diversity(statistics.diversity);
+            population = self.ga.evolve(rng, &population);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::*;
+
+pub trait FitnessTransform: MaybeSendSync {
+    fn transform(&self, fitness: &[f32]) -> Vec<f32>;
+}
+
+impl<F> FitnessTransform for F
+where
+    F: Fn(&[f32]) -> Vec<f32> + MaybeSendSync,
+{
+    fn transform(&self, fitness: &[f32]) -> Vec<f32> {
+        self(fitness)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LinearScaling {
+    multiple: f32,
+}
+
+impl LinearScaling {
+    pub fn new(multiple: f32) -> Self {
+        assert!(multiple >= 1.0);
+        Self { multiple }
+    }
+}
+
+impl Default for LinearScaling {
+    fn default() -> Self {
+        Self::new(2.0)
+    }
+}
+
+impl FitnessTransform for LinearScaling {
+    fn transform(&self, fitness: &[f32]) -> Vec<f32> {
+        let (min, max, avg) = summary(fitness);
+        if max <= avg {
+            return fitness.to_vec();
+        }
+
+        // Keep the average fixed and stretch the best to `multiple` times it,
+        // falling back to mapping the worst onto zero when that would go negative.
+        let (a, b) = if min > (self.multiple * avg - max) / (self.multiple - 1.0) {
+            let delta = max - avg;
+            let a = (self.multiple - 1.0) * avg / delta;
+            (a, avg * (max - self.multiple * avg) / delta)
+        } else {
+            let delta = avg - min;
+            (avg / delta, -min * avg / delta)
+        };
+
+        fitness.iter().map(|value| (a * value + b).max(0.0)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SigmaTruncation {
+    deviations: f32,
+}
+
+impl SigmaTruncation {
+    pub fn new(deviations: f32) -> Self {
+        assert!(deviations >= 0.0);
+        Self { deviations }
+    }
+}
+
+impl Default for SigmaTruncation {
+    fn default() -> Self {
+        Self::new(2.0)
+    }
+}
+
+impl FitnessTransform for SigmaTruncation {
+    fn transform(&self, fitness: &[f32]) -> Vec<f32> {
+        let (_, _, avg) = summary(fitness);
+        let variance = fitness.iter().map(|value| (value - avg).powi(2)).sum::<f32>() / fitness.len().max(1) as f32;
+        let floor = avg - self.deviations * variance.sqrt();
+
+        fitness.iter().map(|value| (value - floor).max(0.0)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PowerLaw {
+    exponent: f32,
+}
+
+impl PowerLaw {
+    pub fn new(exponent: f32) -> Self {
+        assert!(exponent > 0.0);
+        Self { exponent }
+    }
+}
+
+impl FitnessTransform for PowerLaw {
+    fn transform(&self, fitness: &[f32]) -> Vec<f32> {
+        fitness.iter().map(|value| value.max(0.0).powf(self.exponent)).collect()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RankNormalization;
+
+impl RankNormalization {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FitnessTransform for RankNormalization {
+    fn transform(&self, fitness: &[f32]) -> Vec<f32> {
+        let mut order: Vec<usize> = (0..fitness.len()).collect();
+        order.sort_by(|&a, &b| fitness[a].total_cmp(&fitness[b]));
+
+        let mut ranks = vec![0.0; fitness.len()];
+        let mut start = 0;
+
+        while start < order.len() {
+            let end = (start..order.len())
+                .find(|&index| fitness[order[index]] != fitness[order[start]])
+                .unwrap_or(order.len());
+            let rank = (start + end + 1) as f32 / 2.0;
+
+            for &index in &order[start..end] {
+                ranks[index] = rank / fitness.len() as f32;
+            }
+            start = end;
+        }
+
+        ranks
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Windowing;
+
+impl Windowing {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FitnessTransform for Windowing {
+    fn transform(&self, fitness: &[f32]) -> Vec<f32> {
+        let (min, _, _) = summary(fitness);
+        fitness.iter().map(|value| value - min).collect()
+    }
+}
+
+fn summary(fitness: &[f32]) -> (f32, f32, f32) {
+    if fitness.is_empty() {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let min = fitness.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = fitness.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let avg = fitness.iter().sum::<f32>() / fitness.len() as f32;
+
+    (min, max, avg)
+}
@@ -0,0 +1,63 @@
+use crate::*;
+
+pub trait MutationSchedule: MaybeSendSync {
+    fn rates(&self, generation: usize, diversity: &Diversity) -> (f32, f32);
+
+    // Measuring diversity is quadratic in the population, so it's skipped
+    // for schedules that ignore it.
+    fn uses_diversity(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DecaySchedule {
+    chance: f32,
+    coeff: f32,
+    decay: f32,
+}
+
+impl DecaySchedule {
+    pub fn new(chance: f32, coeff: f32, decay: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!((0.0..=1.0).contains(&decay));
+        Self { chance, coeff, decay }
+    }
+}
+
+impl MutationSchedule for DecaySchedule {
+    fn rates(&self, generation: usize, _diversity: &Diversity) -> (f32, f32) {
+        let factor = self.decay.powi(generation as i32);
+        (self.chance * factor, self.coeff * factor)
+    }
+}
+
+/// Boosts the rates while the mean per-gene standard deviation of the
+/// population is below `target_diversity`.
+#[derive(Clone, Debug)]
+pub struct AdaptiveSchedule {
+    chance: f32,
+    coeff: f32,
+    target_diversity: f32,
+    max_boost: f32,
+}
+
+impl AdaptiveSchedule {
+    pub fn new(chance: f32, coeff: f32, target_diversity: f32, max_boost: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(target_diversity > 0.0);
+        assert!(max_boost >= 1.0);
+        Self { chance, coeff, target_diversity, max_boost }
+    }
+}
+
+impl MutationSchedule for AdaptiveSchedule {
+    fn rates(&self, _generation: usize, diversity: &Diversity) -> (f32, f32) {
+        let boost = (self.target_diversity / diversity.mean_deviation().max(f32::EPSILON)).clamp(1.0, self.max_boost);
+        ((self.chance * boost).min(1.0), self.coeff * boost)
+    }
+
+    fn uses_diversity(&self) -> bool {
+        true
+    }
+}
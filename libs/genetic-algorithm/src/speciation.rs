@@ -0,0 +1,116 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Speciation<G = f32> {
+    threshold: f32,
+    representatives: Vec<(usize, Chromosome<G>)>,
+    assignments: Vec<usize>,
+    next_id: usize,
+}
+
+impl<G> Speciation<G> where G: Gene {
+    pub fn new(threshold: f32) -> Self {
+        assert!(threshold > 0.0);
+        Self {
+            threshold,
+            representatives: vec![],
+            assignments: vec![],
+            next_id: 0,
+        }
+    }
+
+    pub fn assignments(&self) -> &[usize] {
+        &self.assignments
+    }
+
+    pub fn species_count(&self) -> usize {
+        self.representatives.len()
+    }
+
+    // Groups the population against the current representatives without
+    // moving them.
+    pub(crate) fn classify<I>(&self, population: &[I]) -> Vec<Vec<usize>>
+    where
+        I: Individual<Gene = G>,
+    {
+        self.clone().speciate(population)
+    }
+
+    pub(crate) fn speciate<I>(&mut self, population: &[I]) -> Vec<Vec<usize>>
+    where
+        I: Individual<Gene = G>,
+    {
+        let mut species: Vec<(usize, Chromosome<G>, Vec<usize>)> = self.representatives
+            .drain(..)
+            .map(|(id, representative)| (id, representative, vec![]))
+            .collect();
+
+        for (index, individual) in population.iter().enumerate() {
+            let chromosome = individual.chromosome();
+
+            match species.iter_mut().find(|(_, representative, _)| representative.distance(chromosome) <= self.threshold) {
+                Some((_, _, members)) => members.push(index),
+                None => {
+                    species.push((self.next_id, chromosome.clone(), vec![index]));
+                    self.next_id += 1;
+                }
+            }
+        }
+
+        species.retain(|(_, _, members)| !members.is_empty());
+
+        self.assignments = vec![0; population.len()];
+        for (id, _, members) in &species {
+            for &member in members {
+                self.assignments[member] = *id;
+            }
+        }
+
+        self.representatives = species
+            .iter()
+            .map(|(id, _, members)| (*id, population[members[0]].chromosome().clone()))
+            .collect();
+
+        species.into_iter().map(|(_, _, members)| members).collect()
+    }
+}
+
+pub(crate) fn share_fitness(species: &[Vec<usize>], fitness: &[f32]) -> Vec<f32> {
+    let mut shared = vec![0.0; fitness.len()];
+
+    for members in species {
+        for &member in members {
+            shared[member] = fitness[member] / members.len() as f32;
+        }
+    }
+
+    shared
+}
+
+// Species with negative shared fitness get no weight of their own, so the
+// floored quotas never exceed `total`.
+pub(crate) fn allocate_offspring(species: &[Vec<usize>], shared: &[f32], total: usize) -> Vec<usize> {
+    let mut weights: Vec<f32> = species
+        .iter()
+        .map(|members| members.iter().map(|&member| shared[member]).sum::<f32>().max(0.0))
+        .collect();
+
+    if weights.iter().sum::<f32>() <= 0.0 {
+        weights = species.iter().map(|members| members.len() as f32).collect();
+    }
+
+    let sum: f32 = weights.iter().sum();
+    let exact: Vec<f32> = weights.iter().map(|weight| weight / sum * total as f32).collect();
+    let mut quotas: Vec<usize> = exact.iter().map(|quota| quota.floor() as usize).collect();
+
+    let mut remainders: Vec<usize> = (0..species.len()).collect();
+    remainders.sort_by(|&a, &b| (exact[b] - quotas[b] as f32).total_cmp(&(exact[a] - quotas[a] as f32)));
+
+    let missing = total - quotas.iter().sum::<usize>();
+    for &index in remainders.iter().take(missing) {
+        quotas[index] += 1;
+    }
+
+    quotas
+}
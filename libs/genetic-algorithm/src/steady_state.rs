@@ -0,0 +1,61 @@
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Replacement {
+    Worst,
+    Oldest,
+}
+
+impl<S, G> GeneticAlgorithm<S, G> where S: SelectionMethod, G: Gene {
+    pub fn offspring<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> I
+    where
+        I: Individual<Gene = G>,
+    {
+        // Every `population.len()` births make up one steady-state generation.
+        let generation_start = self.births.is_multiple_of(population.len());
+        if generation_start {
+            self.record(population);
+        }
+
+        let fitness = self.transform(population.iter().map(|individual| individual.fitness()).collect());
+        let (pools, slots) = self.breeding_pools(population, fitness, generation_start);
+        let parents: Vec<_> = population.iter().map(|individual| individual.chromosome()).collect();
+
+        let id = self.reserve_ids(1);
+        let pool = slots.choose(rng).expect("population must not be empty");
+        let child = self.spawn(rng, &parents, &pools[*pool], id);
+
+        self.births += 1;
+        if self.births.is_multiple_of(population.len()) {
+            self.generation += 1;
+        }
+
+        child
+    }
+
+    pub fn steady_state<I>(&mut self, rng: &mut dyn RngCore, population: &mut [I], replacement: Replacement) -> usize
+    where
+        I: Individual<Gene = G>,
+    {
+        self.birth_stamps.resize(population.len(), 0);
+
+        let index = match replacement {
+            Replacement::Worst => population
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.fitness().total_cmp(&b.fitness()))
+                .map(|(index, _)| index)
+                .expect("population must not be empty"),
+            Replacement::Oldest => self.birth_stamps
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, &stamp)| stamp)
+                .map(|(index, _)| index)
+                .expect("population must not be empty"),
+        };
+
+        population[index] = self.offspring(rng, population);
+        self.birth_stamps[index] = self.births;
+        index
+    }
+}
@@ -62,6 +62,13 @@ impl Network {
         }
         Self { layers }
     }
+
+    pub fn neuron_sizes(layers: &[LayerTopology]) -> Vec<usize> {
+        layers
+            .windows(2)
+            .flat_map(|layers| std::iter::repeat_n(layers[0].neurons + 1, layers[1].neurons))
+            .collect()
+    }
 }
 
 impl Layer {
@@ -160,5 +167,20 @@ mod tests {
         
         assert_eq!(neuron.propagate(&[1.0, 1.0, 1.0]), 4.0);
     }
+
+    #[test]
+    fn neuron_sizes_match_weights_layout() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let layers = [
+            LayerTopology { neurons: 3 },
+            LayerTopology { neurons: 2 },
+            LayerTopology { neurons: 1 },
+        ];
+        let network = Network::random(&mut rng, &layers);
+        let sizes = Network::neuron_sizes(&layers);
+
+        assert_eq!(sizes, vec![4, 4, 3]);
+        assert_eq!(sizes.iter().sum::<usize>(), network.weights().count());
+    }
     
 }
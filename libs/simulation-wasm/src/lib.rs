@@ -5,22 +5,38 @@ use serde::Serialize;
 use serde_wasm_bindgen::to_value;
 
 
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date)]
+    fn now() -> f64;
+}
+
 #[wasm_bindgen]
 pub struct Simulation {
-    rng: ThreadRng,
+    rng: sim::ChaCha8Rng,
     sim: sim::Simulation,
+    seed: u64,
 }
 
 
 #[wasm_bindgen]
 impl Simulation {
     #[wasm_bindgen(constructor)]
-    pub fn new() -> Self {
+    pub fn new(seed: Option<u64>, config: Option<String>) -> Result<Simulation, JsValue> {
         console_error_panic_hook::set_once();
-        let mut rng = thread_rng();
-        let sim = sim::Simulation::random(&mut rng);
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let config = match config {
+            Some(json) => sim::Config::from_json(&json).map_err(to_js_error)?,
+            None => sim::Config::default(),
+        };
+        let mut rng = sim::ChaCha8Rng::seed_from_u64(seed);
+        let sim = sim::Simulation::new(&mut rng, config).map_err(to_js_error)?;
 
-        Self { rng, sim }
+        Ok(Self { rng, sim, seed })
+    }
+    
+    pub fn seed(&self) -> u64 {
+        self.seed
     }
     
     pub fn world(&self) -> JsValue {
@@ -32,6 +48,41 @@ impl Simulation {
     pub fn step(&mut self) {
         self.sim.step(&mut self.rng);
     }
+    
+    pub fn step_n(&mut self, n: usize) {
+        self.sim.step_n(&mut self.rng, n);
+    }
+    
+    pub fn step_for(&mut self, millis: f64) -> usize {
+        let deadline = now() + millis;
+        self.sim.step_while(&mut self.rng, || now() < deadline)
+    }
+    
+    pub fn train_generation(&mut self) -> JsValue {
+        to_value(&self.sim.train_generation(&mut self.rng)).unwrap()
+    }
+    
+    pub fn set_selection(&mut self, name: &str) -> Result<(), JsValue> {
+        let selection = name.parse().map_err(to_js_error)?;
+        self.sim.set_selection(selection);
+        Ok(())
+    }
+    
+    pub fn diversity(&self) -> JsValue {
+        to_value(&self.sim.diversity()).unwrap()
+    }
+    
+    pub fn genealogy_json(&self) -> String {
+        self.sim.genealogy().to_json()
+    }
+    
+    pub fn genealogy_dot(&self) -> String {
+        self.sim.genealogy().to_dot()
+    }
+}
+
+fn to_js_error(err: sim::ga::ConfigError) -> JsValue {
+    JsValue::from_str(&err.to_string())
 }
 
 
@@ -53,6 +104,8 @@ pub struct Animal {
     pub x: f32,
     pub y: f32,
     pub rotation: f32,
+    pub id: Option<u64>,
+    pub parents: Vec<u64>,
 }
 
 
@@ -88,6 +141,8 @@ impl From<&sim::Animal> for Animal {
             x: animal.position().x,
             y: animal.position().y,
             rotation: animal.rotation().angle(),
+            id: animal.id(),
+            parents: animal.parents().to_vec(),
         }
     }
 }
\ No newline at end of file
@@ -9,19 +9,25 @@ pub struct Animal {
     pub(crate) eye: Eye,
     pub(crate) brain: Brain,
     pub(crate) satiation: usize,
+    pub(crate) distance: f32,
+    pub(crate) strategy: Vec<f32>,
+    pub(crate) lineage: Option<ga::Lineage>,
 }
 
 impl Animal {
-    pub(crate) fn new(position: na::Point2<f32>, rotation: na::Rotation2<f32>, speed: f32, rng: &mut dyn RngCore) -> Self {
-        let eye = Eye::default();
-        let brain = Brain::random(rng, &eye);
+    pub(crate) fn new(config: &Config, position: na::Point2<f32>, rotation: na::Rotation2<f32>, rng: &mut dyn RngCore) -> Self {
+        let eye = config.eye();
+        let brain = Brain::random(rng, config);
         Self {
             position,
             rotation,
-            speed,
+            speed: config.spawn_speed,
             eye,
             brain,
             satiation: 0,
+            distance: 0.0,
+            strategy: vec![],
+            lineage: None,
         }
     }
     
@@ -34,20 +40,54 @@ impl Animal {
         &self.rotation
     }
     
+    pub fn id(&self) -> Option<u64> {
+        self.lineage.as_ref().map(ga::Lineage::id)
+    }
+    
+    pub fn parents(&self) -> &[u64] {
+        self.lineage.as_ref().map(ga::Lineage::parents).unwrap_or_default()
+    }
+    
+    pub(crate) fn steer(&mut self, config: &Config, vision: Vec<f32>) {
+        let response = self.brain.nn.propagate(vision);
+        let speed = response[0].clamp(-config.speed_accel, config.speed_accel);
+        let rotation = response[1].clamp(-config.rotation_accel, config.rotation_accel);
+        
+        self.speed = (self.speed + speed).clamp(config.speed_min, config.speed_max);
+        self.rotation = math::rotation(math::angle(&self.rotation) + rotation);
+    }
+    
+    pub(crate) fn advance(&mut self) {
+        self.position += self.rotation * na::Vector2::new(0.0, self.speed);
+        self.distance += self.speed;
+        
+        self.position.x = na::wrap(self.position.x, 0.0, 1.0);
+        self.position.y = na::wrap(self.position.y, 0.0, 1.0);
+    }
+    
     pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
-        self.brain.as_chromosome()
+        let chromosome = self.brain.as_chromosome().with_strategy(self.strategy.clone());
+        match &self.lineage {
+            Some(lineage) => chromosome.with_lineage(lineage.clone()),
+            None => chromosome,
+        }
     }
     
-    pub(crate) fn from_chromosome(chromosome: ga::Chromosome, rng: &mut dyn RngCore, position: na::Point2<f32>) -> Self {
-        let eye = Eye::default();
-        let brain = Brain::from_chromosome(chromosome, &eye);
+    pub(crate) fn from_chromosome(config: &Config, chromosome: ga::Chromosome, rng: &mut dyn RngCore, position: na::Point2<f32>) -> Self {
+        let eye = config.eye();
+        let strategy = chromosome.strategy().to_vec();
+        let lineage = chromosome.lineage().cloned();
+        let brain = Brain::from_chromosome(chromosome, config);
         Self {
             position: position,
-            rotation: rng.gen(),
-            speed: 0.01,
+            rotation: math::random_rotation(rng),
+            speed: config.offspring_speed,
             eye,
             brain,
             satiation: 0,
+            distance: 0.0,
+            strategy,
+            lineage,
         }
     }
 }
\ No newline at end of file
@@ -4,13 +4,19 @@ use crate::*;
 #[derive(Debug)]
 pub struct AnimalIndividual {
   fitness: f32,
+  distance: f32,
+  position: na::Point2<f32>,
   chromosome: ga::Chromosome,
 }
 
 impl ga::Individual for AnimalIndividual {
+  type Gene = f32;
+  
   fn create(chromosome: ga::Chromosome) -> Self {
     Self {
       fitness: 0.0,
+      distance: 0.0,
+      position: na::Point2::origin(),
       chromosome,
     }
   }
@@ -24,15 +30,38 @@ impl ga::Individual for AnimalIndividual {
   }
 }
 
+impl ga::MultiObjective for AnimalIndividual {
+  fn objectives(&self) -> Vec<f32> {
+    vec![self.fitness, -self.distance]
+  }
+}
+
+impl ga::Behaviour for AnimalIndividual {
+  fn behaviour(&self) -> Vec<f32> {
+    vec![self.position.x, self.position.y]
+  }
+}
+
 impl AnimalIndividual {
   pub fn from_animal(animal: &Animal) -> Self {
     Self {
       fitness: animal.satiation as f32,
+      distance: animal.distance,
+      position: animal.position,
       chromosome: animal.as_chromosome(),
     }
   }
   
-  pub fn into_animal(self, rng: &mut dyn RngCore, position: na::Point2<f32>) -> Animal {
-    Animal::from_chromosome(self.chromosome, rng, position)
+  pub fn lineage(&self) -> Option<&ga::Lineage> {
+    self.chromosome.lineage()
+  }
+  
+  pub(crate) fn with_lineage(mut self, lineage: ga::Lineage) -> Self {
+    self.chromosome = self.chromosome.with_lineage(lineage);
+    self
+  }
+  
+  pub fn into_animal(self, config: &Config, rng: &mut dyn RngCore, position: na::Point2<f32>) -> Animal {
+    Animal::from_chromosome(config, self.chromosome, rng, position)
   }
 }
\ No newline at end of file
@@ -7,9 +7,9 @@ pub struct Brain {
 
 
 impl Brain {
-    pub fn random(rng: &mut dyn RngCore, eye: &Eye) -> Self {
+    pub fn random(rng: &mut dyn RngCore, config: &Config) -> Self {
         Self {
-          nn: nn::Network::random(rng, &Self::topology(eye)),
+          nn: nn::Network::random(rng, &config.topology()),
         }
     }
     
@@ -17,23 +17,13 @@ impl Brain {
         self.nn.weights().collect()
     }
     
-    pub(crate) fn from_chromosome(chromosome: ga::Chromosome, eye: &Eye) -> Self {
+    pub(crate) fn from_chromosome(chromosome: ga::Chromosome, config: &Config) -> Self {
         Self {
-            nn: nn::Network::from_weights(chromosome, &Self::topology(eye)),
+            nn: nn::Network::from_weights(chromosome, &config.topology()),
         }
     }
     
-    fn topology(eye: &Eye) -> [nn::LayerTopology; 3] {
-        [
-            nn::LayerTopology {
-                neurons: eye.cells(),
-            },
-            nn::LayerTopology {
-                neurons: 2 * eye.cells(),
-            },
-            nn::LayerTopology {
-                neurons: 2,
-            },
-        ]
+    pub(crate) fn neuron_sizes(config: &Config) -> Vec<usize> {
+        nn::Network::neuron_sizes(&config.topology())
     }
-}
\ No newline at end of file
+}
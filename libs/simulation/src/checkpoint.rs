@@ -0,0 +1,134 @@
+use crate::*;
+use ga::check;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckpointError {
+    Optimizer,
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Optimizer => write!(f, "the state of an external optimizer cannot be checkpointed"),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationCheckpoint<R> {
+    pub ga: ga::Checkpoint<R>,
+    config: Config,
+    animals: Vec<AnimalState>,
+    foods: Vec<na::Point2<f32>>,
+    age: usize,
+    mode: EvolutionMode,
+    genealogy: Genealogy,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct AnimalState {
+    position: na::Point2<f32>,
+    rotation: na::Rotation2<f32>,
+    speed: f32,
+    satiation: usize,
+    distance: f32,
+}
+
+impl Simulation {
+    // Resuming without the optimizer's state would silently continue as a
+    // plain GA, so optimizer-driven runs refuse to checkpoint.
+    pub fn checkpoint<R>(&self, rng: &R) -> Result<SimulationCheckpoint<R>, CheckpointError>
+    where
+        R: Clone,
+    {
+        if self.optimizer.is_some() {
+            return Err(CheckpointError::Optimizer);
+        }
+        
+        let population: Vec<_> = self.world.animals
+            .iter()
+            .map(AnimalIndividual::from_animal)
+            .collect();
+        
+        Ok(SimulationCheckpoint {
+            ga: self.ga.checkpoint(&population, rng),
+            config: self.config.clone(),
+            animals: self.world.animals
+                .iter()
+                .map(|animal| AnimalState {
+                    position: animal.position,
+                    rotation: animal.rotation,
+                    speed: animal.speed,
+                    satiation: animal.satiation,
+                    distance: animal.distance,
+                })
+                .collect(),
+            foods: self.world.foods.iter().map(|food| food.position).collect(),
+            age: self.age,
+            mode: self.mode.clone(),
+            genealogy: self.genealogy.clone(),
+        })
+    }
+    
+    // Checkpoints come from disk and may have been edited, so they're checked
+    // as thoroughly as a fresh config before anything is built from them.
+    pub fn restore<R>(checkpoint: SimulationCheckpoint<R>) -> Result<(Self, R), ga::ConfigError> {
+        checkpoint.config.validate()?;
+
+        let population = checkpoint.ga.population.len();
+        let weights: usize = Brain::neuron_sizes(&checkpoint.config).iter().sum();
+        check("population", population == checkpoint.config.animals as usize, "must hold one chromosome per animal")?;
+        check("animals", checkpoint.animals.len() == population, "must hold one state per chromosome")?;
+        check(
+            "population",
+            checkpoint.ga.population.iter().all(|(chromosome, _)| chromosome.len() == weights),
+            "chromosomes must match the brain topology",
+        )?;
+
+        let mut ga = Self::genetic_algorithm(&checkpoint.config)?;
+        ga.restore(&checkpoint.ga);
+        
+        let animals = checkpoint.ga.population
+            .into_iter()
+            .zip(checkpoint.animals)
+            .map(|((chromosome, _), state)| {
+                let eye = checkpoint.config.eye();
+                let strategy = chromosome.strategy().to_vec();
+                let lineage = chromosome.lineage().cloned();
+                let brain = Brain::from_chromosome(chromosome, &checkpoint.config);
+                
+                Animal {
+                    position: state.position,
+                    rotation: state.rotation,
+                    speed: state.speed,
+                    eye,
+                    brain,
+                    satiation: state.satiation,
+                    distance: state.distance,
+                    strategy,
+                    lineage,
+                }
+            })
+            .collect();
+        
+        let simulation = Self {
+            world: World {
+                animals,
+                predators: vec![],
+                foods: checkpoint.foods.into_iter().map(Food::new).collect(),
+            },
+            ga,
+            config: checkpoint.config,
+            age: checkpoint.age,
+            mode: checkpoint.mode,
+            optimizer: None,
+            genealogy: checkpoint.genealogy,
+            statistics: None,
+        };
+        
+        Ok((simulation, checkpoint.ga.rng))
+    }
+}
@@ -0,0 +1,173 @@
+use crate::*;
+
+pub struct Coevolution<S = ga::SelectionConfig, T = ga::SelectionConfig> {
+    world: World,
+    prey_config: Config,
+    predator_config: Config,
+    prey_ga: ga::GeneticAlgorithm<S>,
+    predator_ga: ga::GeneticAlgorithm<T>,
+    age: usize,
+}
+
+impl Coevolution {
+    pub fn random(rng: &mut dyn RngCore) -> Self {
+        let prey_config = Config::default();
+        let predator_config = Config {
+            animals: 8,
+            speed_max: 0.006,
+            eat_radius: 0.02,
+            ..Config::default()
+        };
+        let prey_ga = Simulation::genetic_algorithm(&prey_config).expect("default config is valid");
+        let predator_ga = Simulation::genetic_algorithm(&predator_config).expect("default config is valid");
+        Self::new(rng, prey_config, predator_config, prey_ga, predator_ga).expect("default config is valid")
+    }
+}
+
+// Each population runs on its own config: a predator config's `animals` is
+// the number of predators and its `eat_radius` is how close it has to get to
+// catch prey. The world, food and generation length follow the prey config.
+impl<S, T> Coevolution<S, T>
+where
+    S: ga::SelectionMethod,
+    T: ga::SelectionMethod,
+{
+    pub fn new(
+        rng: &mut dyn RngCore,
+        prey_config: Config,
+        predator_config: Config,
+        prey_ga: ga::GeneticAlgorithm<S>,
+        predator_ga: ga::GeneticAlgorithm<T>,
+    ) -> Result<Self, ga::ConfigError> {
+        prey_config.validate()?;
+        predator_config.validate()?;
+
+        let mut world = World::new();
+        world.animals = Self::spawn(&mut world, &prey_config, rng);
+        world.predators = Self::spawn(&mut world, &predator_config, rng);
+        world.foods = world
+            .generate_poison(rng, 1.0, 1.0, prey_config.foods, prey_config.food_spacing)
+            .into_iter()
+            .map(Food::new)
+            .collect();
+
+        Ok(Self {
+            world,
+            prey_config,
+            predator_config,
+            prey_ga,
+            predator_ga,
+            age: 0,
+        })
+    }
+
+    fn spawn(world: &mut World, config: &Config, rng: &mut dyn RngCore) -> Vec<Animal> {
+        world
+            .generate_poison(rng, 1.0, 1.0, config.animals, config.animal_spacing)
+            .into_iter()
+            .map(|position| Animal::new(config, position, math::random_rotation(rng), rng))
+            .collect()
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn prey_config(&self) -> &Config {
+        &self.prey_config
+    }
+
+    pub fn predator_config(&self) -> &Config {
+        &self.predator_config
+    }
+
+    pub fn prey_ga(&self) -> &ga::GeneticAlgorithm<S> {
+        &self.prey_ga
+    }
+
+    pub fn predator_ga(&self) -> &ga::GeneticAlgorithm<T> {
+        &self.predator_ga
+    }
+
+    pub fn step(&mut self, rng: &mut dyn RngCore) {
+        self.process_collisions(rng);
+        self.process_brains();
+
+        for animal in self.world.animals.iter_mut().chain(&mut self.world.predators) {
+            animal.advance();
+        }
+
+        self.age += 1;
+        if self.age >= self.prey_config.generation_length {
+            self.evolve(rng);
+        }
+    }
+
+    fn process_brains(&mut self) {
+        let prey: Vec<_> = self.world.animals.iter().map(|animal| animal.position).collect();
+        let predators: Vec<_> = self.world.predators.iter().map(|animal| animal.position).collect();
+
+        // Prey perceive food as attraction and predators as repulsion through the same
+        // eye cells, so each population keeps the brain topology of its own config.
+        for animal in &mut self.world.animals {
+            let food = animal.eye.process_vision(animal.position, animal.rotation, &self.world.foods);
+            let threat = animal.eye.process_targets(animal.position, animal.rotation, predators.iter().copied());
+            let vision = food.iter().zip(threat).map(|(food, threat)| food - threat).collect();
+            animal.steer(&self.prey_config, vision);
+        }
+
+        for predator in &mut self.world.predators {
+            let vision = predator.eye.process_targets(predator.position, predator.rotation, prey.iter().copied());
+            predator.steer(&self.predator_config, vision);
+        }
+    }
+
+    fn process_collisions(&mut self, rng: &mut dyn RngCore) {
+        for animal in &mut self.world.animals {
+            for food in &mut self.world.foods {
+                if na::distance(&animal.position, &food.position) <= self.prey_config.eat_radius {
+                    animal.satiation += 1;
+                    food.position = rng.gen();
+                }
+            }
+        }
+
+        for predator in &mut self.world.predators {
+            for prey in &mut self.world.animals {
+                if na::distance(&predator.position, &prey.position) <= self.predator_config.eat_radius {
+                    predator.satiation += 1;
+                    prey.satiation = 0;
+                    prey.position = rng.gen();
+                }
+            }
+        }
+    }
+
+    fn evolve(&mut self, rng: &mut dyn RngCore) {
+        self.age = 0;
+
+        let prey = Self::breed(&mut self.prey_ga, rng, &self.world.animals);
+        let predators = Self::breed(&mut self.predator_ga, rng, &self.world.predators);
+
+        self.world.animals = Self::place(&mut self.world, &self.prey_config, rng, prey);
+        self.world.predators = Self::place(&mut self.world, &self.predator_config, rng, predators);
+    }
+
+    fn breed<U>(ga: &mut ga::GeneticAlgorithm<U>, rng: &mut dyn RngCore, animals: &[Animal]) -> Vec<AnimalIndividual>
+    where
+        U: ga::SelectionMethod,
+    {
+        let population: Vec<_> = animals.iter().map(AnimalIndividual::from_animal).collect();
+        ga.evolve(rng, &population)
+    }
+
+    fn place(world: &mut World, config: &Config, rng: &mut dyn RngCore, population: Vec<AnimalIndividual>) -> Vec<Animal> {
+        let positions = world.generate_poison(rng, 1.0, 1.0, population.len() as u32, config.animal_spacing);
+
+        population
+            .into_iter()
+            .zip(positions)
+            .map(|(individual, position)| individual.into_animal(config, rng, position))
+            .collect()
+    }
+}
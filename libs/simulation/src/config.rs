@@ -0,0 +1,156 @@
+use crate::*;
+use ga::check;
+
+const MIN_SPACING: f32 = 0.005;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub speed_min: f32,
+    pub speed_max: f32,
+    pub speed_accel: f32,
+    pub rotation_accel: f32,
+    pub spawn_speed: f32,
+    pub offspring_speed: f32,
+    pub generation_length: usize,
+    pub animals: u32,
+    pub foods: u32,
+    pub animal_spacing: f32,
+    pub food_spacing: f32,
+    pub eat_radius: f32,
+    pub fov_range: f32,
+    pub fov_angle: f32,
+    pub eye_cells: usize,
+    pub hidden_layers: Vec<usize>,
+    pub mode: ModeConfig,
+    pub lifecycle: Lifecycle,
+    pub ga: ga::GaConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModeConfig {
+    #[default]
+    Fitness,
+    MultiObjective,
+    Novelty { neighbours: usize, threshold: f32, weight: f32 },
+}
+
+impl ModeConfig {
+    pub(crate) fn build(&self) -> EvolutionMode {
+        match *self {
+            Self::Fitness => EvolutionMode::Fitness,
+            Self::MultiObjective => EvolutionMode::MultiObjective,
+            Self::Novelty { neighbours, threshold, weight } => {
+                EvolutionMode::Novelty(ga::NoveltySearch::new(neighbours, threshold, weight))
+            }
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let eye = Eye::default();
+        Self {
+            speed_min: 0.001,
+            speed_max: 0.005,
+            speed_accel: 0.2,
+            rotation_accel: FRAC_PI_2,
+            spawn_speed: 0.002,
+            offspring_speed: 0.01,
+            generation_length: 2500,
+            animals: 35,
+            foods: 50,
+            animal_spacing: 0.1,
+            food_spacing: 0.05,
+            eat_radius: 0.01,
+            fov_range: eye.fov_range,
+            fov_angle: eye.fov_angle,
+            eye_cells: eye.cells,
+            hidden_layers: vec![2 * eye.cells],
+            mode: ModeConfig::default(),
+            lifecycle: Lifecycle::default(),
+            ga: ga::GaConfig {
+                crossover: ga::CrossoverConfig::Neuron { neurons: vec![] },
+                mutation: ga::MutationConfig::Gaussian { chance: 0.01, coeff: 0.3 },
+                transforms: vec![ga::TransformConfig::LinearScaling { multiple: 2.0 }],
+                hall_of_fame: 10,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Config {
+    pub fn from_toml(source: &str) -> Result<Self, ga::ConfigError> {
+        let config: Self = toml::from_str(source).map_err(|err| ga::ConfigError::Parse(err.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, ga::ConfigError> {
+        let config: Self = serde_json::from_str(source).map_err(|err| ga::ConfigError::Parse(err.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ga::ConfigError> {
+        check("speed_min", self.speed_min >= 0.0, "must not be negative")?;
+        check("speed_max", self.speed_min <= self.speed_max, "must not be less than `speed_min`")?;
+        check("speed_accel", self.speed_accel >= 0.0, "must not be negative")?;
+        check("rotation_accel", self.rotation_accel >= 0.0, "must not be negative")?;
+        check("generation_length", self.generation_length > 0, "must be positive")?;
+        check("animals", self.animals > 0, "must be positive")?;
+        check("eat_radius", self.eat_radius >= 0.0, "must not be negative")?;
+        check("fov_range", self.fov_range > 0.0, "must be positive")?;
+        check("fov_angle", self.fov_angle > 0.0, "must be positive")?;
+        check("eye_cells", self.eye_cells > 0, "must be positive")?;
+        check("hidden_layers", self.hidden_layers.iter().all(|&neurons| neurons > 0), "every layer needs a neuron")?;
+
+        if let ModeConfig::Novelty { neighbours, threshold, weight } = self.mode {
+            check("mode.neighbours", neighbours > 0, "must be positive")?;
+            check("mode.threshold", threshold >= 0.0, "must not be negative")?;
+            check("mode.weight", (0.0..=1.0).contains(&weight), "must be between 0 and 1")?;
+        }
+        if let Lifecycle::SteadyState { lifespan } = self.lifecycle {
+            check("lifecycle.lifespan", lifespan >= 1.0, "must be at least 1")?;
+        }
+
+        // Spawn points are drawn until all of them fit, so an overfull world
+        // would never finish placing them.
+        fits("animal_spacing", self.animals, self.animal_spacing)?;
+        fits("food_spacing", self.foods, self.food_spacing)?;
+
+        self.resolved_ga().validate()
+    }
+
+    // An empty neuron list in the GA config means "split along this brain's neurons".
+    pub(crate) fn resolved_ga(&self) -> ga::GaConfig {
+        let mut ga = self.ga.clone();
+        if let ga::CrossoverConfig::Neuron { neurons } = &mut ga.crossover {
+            if neurons.is_empty() {
+                *neurons = Brain::neuron_sizes(self);
+            }
+        }
+        ga
+    }
+
+    pub(crate) fn eye(&self) -> Eye {
+        Eye::new(self.fov_range, self.fov_angle, self.eye_cells)
+    }
+
+    pub(crate) fn topology(&self) -> Vec<nn::LayerTopology> {
+        std::iter::once(self.eye_cells)
+            .chain(self.hidden_layers.iter().copied())
+            .chain(std::iter::once(2))
+            .map(|neurons| nn::LayerTopology { neurons })
+            .collect()
+    }
+}
+
+// Placement keeps a grid of `spacing / √2` cells, which grows quadratically
+// as the spacing shrinks.
+fn fits(field: &'static str, count: u32, spacing: f32) -> Result<(), ga::ConfigError> {
+    check(field, spacing >= MIN_SPACING, "must be at least 0.005")?;
+    check(field, count as f32 * spacing * spacing <= 0.5, "too many points to place this far apart")
+}
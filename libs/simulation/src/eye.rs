@@ -31,18 +31,27 @@ impl Eye {
 
   
   pub fn process_vision(&self, position: na::Point2<f32>, rotation: na::Rotation2<f32>, foods: &[Food]) -> Vec<f32> {
+    self.process_targets(position, rotation, foods.iter().map(|food| food.position))
+  }
+  
+  pub fn process_targets(
+    &self,
+    position: na::Point2<f32>,
+    rotation: na::Rotation2<f32>,
+    targets: impl IntoIterator<Item = na::Point2<f32>>,
+  ) -> Vec<f32> {
     let mut cells = vec![0.0; self.cells];
     
-    for food in foods {
-      let vec = food.position - position;
+    for target in targets {
+      let vec = target - position;
       let distance = vec.norm();
       
       if distance > self.fov_range {
         continue;
       }
       
-      let angle = na::Rotation2::rotation_between(&na::Vector2::y(), &vec).angle();
-      let angle = angle - rotation.angle();
+      let angle = libm::atan2f(-vec.x, vec.y);
+      let angle = angle - math::angle(&rotation);
       let angle = na::wrap(angle, -PI, PI);
       
       if angle < -self.fov_angle || angle > self.fov_angle {
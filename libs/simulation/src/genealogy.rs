@@ -0,0 +1,82 @@
+use crate::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ancestor {
+    pub parents: Vec<u64>,
+    pub generation: usize,
+    pub fitness: Option<f32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Genealogy {
+    nodes: BTreeMap<u64, Ancestor>,
+}
+
+impl Genealogy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Ancestor> {
+        self.nodes.get(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn ancestors(&self, id: u64) -> Vec<u64> {
+        let mut seen = BTreeSet::new();
+        let mut pending = vec![id];
+
+        while let Some(id) = pending.pop() {
+            for &parent in self.nodes.get(&id).map(|node| node.parents.as_slice()).unwrap_or_default() {
+                if seen.insert(parent) {
+                    pending.push(parent);
+                }
+            }
+        }
+
+        seen.into_iter().collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.nodes).expect("genealogy is always serializable")
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph genealogy {\n");
+
+        for (id, node) in &self.nodes {
+            let fitness = node.fitness.map(|fitness| format!("\\nfitness {}", fitness)).unwrap_or_default();
+            writeln!(dot, "    {} [label=\"#{} gen {}{}\"];", id, id, node.generation, fitness).unwrap();
+
+            for parent in &node.parents {
+                writeln!(dot, "    {} -> {};", parent, id).unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub(crate) fn record(&mut self, lineage: &ga::Lineage, generation: usize) {
+        self.nodes.insert(lineage.id(), Ancestor {
+            parents: lineage.parents().to_vec(),
+            generation,
+            fitness: None,
+        });
+    }
+
+    pub(crate) fn score(&mut self, animal: &Animal) {
+        if let Some(node) = animal.id().and_then(|id| self.nodes.get_mut(&id)) {
+            node.fitness = Some(animal.satiation as f32);
+        }
+    }
+}
@@ -1,65 +1,224 @@
 use nalgebra as na;
-use rand::{Rng, RngCore};
+use rand::{Rng, RngCore, SeedableRng};
+pub use rand_chacha::ChaCha8Rng;
 use std::f32::consts::SQRT_2;
 use std::ops::Index;
+use std::time::{Duration, Instant};
 use lib_neural_network as nn;
-use lib_genetic_algorithm as ga;
+pub use lib_genetic_algorithm as ga;
 use std::f32::consts::FRAC_PI_2;
+use serde::{Deserialize, Serialize};
 
 
-pub use self::{animal::*, brain::*, eye::*, food::*, world::*, animal_individual::*};
+pub use self::{animal::*, brain::*, eye::*, food::*, world::*, animal_individual::*, checkpoint::*, coevolution::*, config::*, genealogy::*};
 
 mod animal;
+mod checkpoint;
+mod coevolution;
+mod config;
 mod food;
+mod genealogy;
+mod math;
 mod world;
 mod eye;
 mod animal_individual;
 mod brain;
 
-const SPEED_MIN: f32 = 0.001;
-const SPEED_MAX: f32 = 0.005;
-
-const SPEED_ACCEL: f32 = 0.2;
-const ROTATION_ACCEL: f32 = FRAC_PI_2;
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum EvolutionMode {
+    Fitness,
+    MultiObjective,
+    Novelty(ga::NoveltySearch),
+}
 
-const GENERATION_LENGTH: usize = 2500;
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Lifecycle {
+    #[default]
+    Generational,
+    SteadyState { lifespan: f32 },
+}
 
 pub struct Simulation {
     world: World,
-    ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
+    ga: ga::GeneticAlgorithm<ga::SelectionConfig>,
+    config: Config,
     age: usize,
-    
+    mode: EvolutionMode,
+    optimizer: Option<Box<dyn ga::Optimizer<AnimalIndividual>>>,
+    genealogy: Genealogy,
+    statistics: Option<ga::Statistics>,
 }
 
 impl Simulation {
+    pub fn with_seed(seed: u64) -> (Self, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (Self::random(&mut rng), rng)
+    }
+    
     pub fn random(rng: &mut dyn RngCore) -> Self {
+        Self::new(rng, Config::default()).expect("default config is valid")
+    }
+    
+    pub fn new(rng: &mut dyn RngCore, config: Config) -> Result<Self, ga::ConfigError> {
+        config.validate()?;
+        let mut ga = Self::genetic_algorithm(&config)?;
+        
         let mut world = World::new();
-        world.random(rng);
-        let ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection::default(),
-            ga::UniformCrossover::default(),
-            ga::GaussianMutation::new(0.01, 0.3),
-        );
+        world.random(rng, &config);
+        
+        let mut genealogy = Genealogy::new();
+        for animal in &mut world.animals {
+            let lineage = ga.founder();
+            genealogy.record(&lineage, 0);
+            animal.lineage = Some(lineage);
+        }
         
-        Self {
+        Ok(Self {
             world,
             ga,
+            mode: config.mode.build(),
+            config,
             age: 0,
-        }
+            optimizer: None,
+            genealogy,
+            statistics: None,
+        })
+    }
+    
+    pub(crate) fn genetic_algorithm(
+        config: &Config,
+    ) -> Result<ga::GeneticAlgorithm<ga::SelectionConfig>, ga::ConfigError> {
+        config.resolved_ga().build()
+    }
+    
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+    
+    pub fn ga_config(&self) -> &ga::GaConfig {
+        &self.config.ga
+    }
+    
+    pub fn set_selection(&mut self, selection: ga::SelectionConfig) {
+        self.ga.set_selection_method(selection);
+        self.config.ga.selection = selection;
+    }
+    
+    pub fn with_optimizer(mut self, optimizer: impl ga::Optimizer<AnimalIndividual> + 'static) -> Self {
+        self.optimizer = Some(Box::new(optimizer));
+        self
     }
     
     pub fn world (&self) -> &World {
         &self.world
     }
     
+    pub fn diversity(&self) -> ga::Diversity {
+        let population: Vec<_> = self.world.animals
+            .iter()
+            .map(AnimalIndividual::from_animal)
+            .collect();
+        
+        ga::Diversity::new(&population)
+    }
+    
+    pub fn genealogy(&self) -> &Genealogy {
+        &self.genealogy
+    }
+    
+    pub fn hall_of_fame(&self) -> &ga::HallOfFame {
+        self.ga.hall_of_fame()
+    }
+    
+    // Statistics of the last generation that was bred, taken before evolving it.
+    pub fn statistics(&self) -> Option<&ga::Statistics> {
+        self.statistics.as_ref()
+    }
+    
+    pub fn pareto_front(&self) -> Vec<Vec<f32>> {
+        self.ga
+            .pareto_front()
+            .map(|(_, objectives)| objectives.to_vec())
+            .collect()
+    }
+    
     pub fn step(&mut self, rng: &mut dyn RngCore) {
         self.process_collisions(rng);
         self.process_brains();
         self.process_movements();
         
         self.age += 1;
-        if self.age >= GENERATION_LENGTH {
-           self.evolve(rng);
+        match self.config.lifecycle {
+            Lifecycle::Generational => {
+                if self.age >= self.config.generation_length {
+                    self.evolve(rng);
+                }
+            }
+            Lifecycle::SteadyState { lifespan } => self.replace_dead(rng, lifespan),
+        }
+    }
+    
+    pub fn step_n(&mut self, rng: &mut dyn RngCore, n: usize) {
+        for _ in 0..n {
+            self.step(rng);
+        }
+    }
+    
+    pub fn step_while(&mut self, rng: &mut dyn RngCore, mut keep_going: impl FnMut() -> bool) -> usize {
+        let mut steps = 0;
+        while keep_going() {
+            self.step(rng);
+            steps += 1;
+        }
+        steps
+    }
+    
+    // `Instant` panics on wasm32, where callers go through `step_while` with a JS clock.
+    pub fn step_for(&mut self, rng: &mut dyn RngCore, budget: Duration) -> usize {
+        let started = Instant::now();
+        self.step_while(rng, || started.elapsed() < budget)
+    }
+    
+    // Steady-state populations never evolve at once, so there a generation is
+    // `generation_length` ticks and the statistics describe the living animals.
+    pub fn train_generation(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
+        match self.config.lifecycle {
+            Lifecycle::Generational => {
+                let remaining = self.config.generation_length.saturating_sub(self.age).max(1);
+                self.step_n(rng, remaining);
+                self.statistics.clone().expect("a generation was just bred")
+            }
+            Lifecycle::SteadyState { .. } => {
+                self.step_n(rng, self.config.generation_length);
+                let population: Vec<_> = self.world.animals
+                    .iter()
+                    .map(AnimalIndividual::from_animal)
+                    .collect();
+                ga::Statistics::new(self.ga.generation(), &population)
+            }
+        }
+    }
+    
+    fn replace_dead(&mut self, rng: &mut dyn RngCore, lifespan: f32) {
+        for index in 0..self.world.animals.len() {
+            if !rng.gen_bool((1.0 / lifespan) as f64) {
+                continue;
+            }
+            
+            let population: Vec<_> = self.world.animals
+                .iter()
+                .map(AnimalIndividual::from_animal)
+                .collect();
+            
+            let child: AnimalIndividual = self.ga.offspring(rng, &population);
+            if let Some(lineage) = child.lineage() {
+                self.genealogy.record(lineage, self.ga.generation());
+            }
+            self.genealogy.score(&self.world.animals[index]);
+            
+            let position = rng.gen();
+            self.world.animals[index] = child.into_animal(&self.config, rng, position);
         }
     }
     
@@ -70,39 +229,64 @@ impl Simulation {
             .iter()
             .map(AnimalIndividual::from_animal)
             .collect();
+        
+        let statistics = ga::Statistics::new(self.ga.generation(), &current_population);
+        self.ga.observe_diversity(statistics.diversity.clone());
+        self.statistics = Some(statistics);
        
-        let new_population = self.ga.evolve(rng, &current_population);
+        let new_population = match &mut self.mode {
+            EvolutionMode::Fitness => match &mut self.optimizer {
+                Some(optimizer) => optimizer
+                    .evolve(rng, &current_population)
+                    .into_iter()
+                    .map(|individual| match individual.lineage() {
+                        Some(_) => individual,
+                        None => individual.with_lineage(self.ga.founder()),
+                    })
+                    .collect(),
+                None => {
+                    #[cfg(feature = "parallel")]
+                    let new_population = self.ga.evolve_parallel(rng, &current_population);
+                    #[cfg(not(feature = "parallel"))]
+                    let new_population = self.ga.evolve(rng, &current_population);
+                    new_population
+                }
+            },
+            EvolutionMode::MultiObjective => self.ga.evolve_multi_objective(rng, &current_population),
+            EvolutionMode::Novelty(search) => {
+                let scores = search.score(&current_population);
+                self.ga.evolve_scored(rng, &current_population, &scores)
+            }
+        };
+        
+        for animal in &self.world.animals {
+            self.genealogy.score(animal);
+        }
+        for individual in &new_population {
+            if let Some(lineage) = individual.lineage() {
+                self.genealogy.record(lineage, self.ga.generation());
+            }
+        }
         
-        let positions = self.world.generate_poison(rng, 1.0, 1.0, 35, 0.1);
+        let positions = self.world.generate_poison(rng, 1.0, 1.0, self.config.animals, self.config.animal_spacing);
         
         self.world.animals = new_population
             .into_iter()
             .zip(positions)
-            .map(|(individual, position)| individual.into_animal(rng, position))
+            .map(|(individual, position)| individual.into_animal(&self.config, rng, position))
             .collect();
-        
-        
     }
     
     fn process_brains(&mut self) {
         for animal in &mut self.world.animals {
             let vision = animal.eye.process_vision(animal.position, animal.rotation, &self.world.foods);
-            let response = animal.brain.nn.propagate(vision);
-            let speed = response[0].clamp(-SPEED_ACCEL, SPEED_ACCEL);
-            
-            let rotation = response[1].clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
-            
-            animal.speed = (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX);
-            animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+            animal.steer(&self.config, vision);
         }
     }
     
     fn process_movements(&mut self) {
         for animal in &mut self.world.animals {
-            animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
-            
-            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+            animal.advance();
         }
     }
     
@@ -114,7 +298,7 @@ impl Simulation {
                     &food.position,
                 );
     
-                if distance <= 0.01 {
+                if distance <= self.config.eat_radius {
                     animal.satiation += 1;
                     food.position = rng.gen();
                 }
@@ -139,4 +323,255 @@ mod tests {
     #[test]
     fn it_works() {
     }
+    
+    #[test]
+    fn checkpoint_resumes_bit_identical() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let mut simulation = Simulation::random(&mut rng);
+        
+        for _ in 0..simulation.config().generation_length - 50 {
+            simulation.step(&mut rng);
+        }
+        
+        let json = serde_json::to_string(&simulation.checkpoint(&rng).unwrap()).unwrap();
+        let (mut restored, mut restored_rng) = Simulation::restore::<ChaCha8Rng>(serde_json::from_str(&json).unwrap()).unwrap();
+        
+        for _ in 0..100 {
+            simulation.step(&mut rng);
+            restored.step(&mut restored_rng);
+        }
+        
+        assert_eq!(
+            serde_json::to_string(&simulation.checkpoint(&rng).unwrap()).unwrap(),
+            serde_json::to_string(&restored.checkpoint(&restored_rng).unwrap()).unwrap(),
+        );
+        assert_eq!(restored.ga.generation(), 1);
+
+        let blind = json.replace(r#""eye_cells":9"#, r#""eye_cells":0"#);
+        assert!(matches!(
+            Simulation::restore::<ChaCha8Rng>(serde_json::from_str(&blind).unwrap()),
+            Err(ga::ConfigError::Invalid { field: "eye_cells", .. }),
+        ));
+
+        let mut truncated: SimulationCheckpoint<ChaCha8Rng> = serde_json::from_str(&json).unwrap();
+        truncated.ga.population.pop();
+        assert!(Simulation::restore(truncated).is_err());
+    }
+    
+    #[test]
+    fn genealogy_traces_back_to_founders() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let config = Config { lifecycle: Lifecycle::SteadyState { lifespan: 5.0 }, ..Config::default() };
+        let mut simulation = Simulation::new(&mut rng, config).unwrap();
+        let founders = simulation.world().animals().len() as u64;
+        
+        for _ in 0..20 {
+            simulation.step(&mut rng);
+        }
+        
+        let genealogy = simulation.genealogy();
+        assert!(genealogy.len() > founders as usize);
+        
+        for animal in simulation.world().animals() {
+            let id = animal.id().unwrap();
+            assert_eq!(genealogy.get(id).unwrap().parents, animal.parents());
+            
+            if id >= founders {
+                assert!(genealogy.ancestors(id).iter().any(|&ancestor| ancestor < founders));
+            }
+        }
+        
+        let json: serde_json::Value = serde_json::from_str(&genealogy.to_json()).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), genealogy.len());
+        assert!(genealogy.to_dot().contains(" -> "));
+    }
+    
+    #[test]
+    fn optimizer_children_enter_the_genealogy() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let config = Config { generation_length: 20, ..Config::default() };
+        let mut simulation = Simulation::new(&mut rng, config).unwrap()
+            .with_optimizer(ga::CmaEs::new(0.3));
+        
+        simulation.train_generation(&mut rng);
+        for animal in simulation.world().animals() {
+            assert!(simulation.genealogy().get(animal.id().unwrap()).is_some());
+        }
+        assert_eq!(simulation.checkpoint(&rng).err(), Some(CheckpointError::Optimizer));
+    }
+    
+    #[test]
+    fn coevolution_evolves_both_populations() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let mut coevolution = Coevolution::random(&mut rng);
+        let prey = coevolution.world().animals().len();
+        let predators = coevolution.world().predators().len();
+        assert_eq!(predators, coevolution.predator_config().animals as usize);
+        
+        for _ in 0..coevolution.prey_config().generation_length {
+            coevolution.step(&mut rng);
+        }
+        
+        assert_eq!(coevolution.prey_ga().generation(), 1);
+        assert_eq!(coevolution.predator_ga().generation(), 1);
+        assert_eq!(coevolution.world().animals().len(), prey);
+        assert_eq!(coevolution.world().predators().len(), predators);
+        
+        // Radii beyond the world's diagonal make every prey eat every food and
+        // every predator catch every prey on each step.
+        let fitness = |animals: &[Animal]| -> Vec<f32> {
+            animals.iter().map(|animal| ga::Individual::fitness(&AnimalIndividual::from_animal(animal))).collect()
+        };
+        let run = |catch_radius: f32| {
+            let mut rng = ChaCha8Rng::seed_from_u64(42);
+            let prey = Config { animals: 5, foods: 4, eat_radius: 1.5, ..Config::default() };
+            let predators = Config { animals: 2, eat_radius: catch_radius, ..Config::default() };
+            let mut coevolution = Coevolution::new(
+                &mut rng,
+                prey.clone(),
+                predators.clone(),
+                Simulation::genetic_algorithm(&prey).unwrap(),
+                Simulation::genetic_algorithm(&predators).unwrap(),
+            )
+            .unwrap();
+            for _ in 0..3 {
+                coevolution.step(&mut rng);
+            }
+            (fitness(coevolution.world().animals()), fitness(coevolution.world().predators()))
+        };
+        
+        assert_eq!(run(0.0), (vec![12.0; 5], vec![0.0; 2]));
+        assert_eq!(run(1.5), (vec![0.0; 5], vec![15.0; 2]));
+        
+        // Predator settings are only checked where predators exist.
+        let config = Config::default();
+        let none = Config { animals: 0, ..Config::default() };
+        let coevolution = Coevolution::new(
+            &mut rng,
+            config.clone(),
+            none,
+            Simulation::genetic_algorithm(&config).unwrap(),
+            Simulation::genetic_algorithm(&config).unwrap(),
+        );
+        assert!(matches!(coevolution, Err(ga::ConfigError::Invalid { field: "animals", .. })));
+    }
+    
+    #[test]
+    fn builds_from_config() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let config = Config::from_toml(r#"
+            animals = 20
+            eye_cells = 5
+            hidden_layers = [8, 4]
+
+            [ga.mutation]
+            type = "creep"
+            chance = 2.0
+            coeff = 0.1
+        "#);
+        assert!(matches!(config, Err(ga::ConfigError::Invalid { field: "mutation.chance", .. })));
+        
+        let config = Config::from_json(r#"{ "speed_min": 0.01, "speed_max": 0.005 }"#);
+        assert!(matches!(config, Err(ga::ConfigError::Invalid { field: "speed_max", .. })));
+        
+        let config = Config::from_json(r#"{ "animal_spacing": 0.0001 }"#);
+        assert!(matches!(config, Err(ga::ConfigError::Invalid { field: "animal_spacing", .. })));
+        
+        let config = Config::from_json(r#"{ "lifecycle": { "type": "steady_state", "lifespan": 0.5 } }"#);
+        assert!(matches!(config, Err(ga::ConfigError::Invalid { field: "lifecycle.lifespan", .. })));
+        
+        let config = Config::from_toml(r#"
+            [mode]
+            type = "novelty"
+            neighbours = 5
+            threshold = 0.1
+            weight = 0.5
+
+            [lifecycle]
+            type = "steady_state"
+            lifespan = 500.0
+        "#).unwrap();
+        assert_eq!(config.lifecycle, Lifecycle::SteadyState { lifespan: 500.0 });
+        let simulation = Simulation::new(&mut rng, config).unwrap();
+        assert!(matches!(simulation.mode, EvolutionMode::Novelty(_)));
+        
+        let config = Config::from_toml(r#"
+            animals = 20
+            eye_cells = 5
+            hidden_layers = [8, 4]
+            generation_length = 30
+
+            [ga.selection]
+            type = "tournament"
+            size = 3
+        "#).unwrap();
+        let mut simulation = Simulation::new(&mut rng, config.clone()).unwrap();
+        assert_eq!(simulation.config(), &config);
+        assert_eq!(simulation.ga_config().selection, ga::SelectionConfig::Tournament { size: 3 });
+        assert_eq!(simulation.world().animals().len(), 20);
+        assert_eq!(simulation.world().animals()[0].eye.cells(), 5);
+        
+        for _ in 0..30 {
+            simulation.step(&mut rng);
+        }
+        assert_eq!(simulation.ga.generation(), 1);
+        assert_eq!(simulation.world().animals().len(), 20);
+        assert_eq!(simulation.statistics().unwrap().generation, 0);
+    }
+    
+    #[test]
+    fn fast_forwards_to_the_next_generation() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let config = Config { generation_length: 50, ..Config::default() };
+        let mut simulation = Simulation::new(&mut rng, config).unwrap();
+        
+        simulation.step_n(&mut rng, 20);
+        assert_eq!(simulation.age, 20);
+        
+        let statistics = simulation.train_generation(&mut rng);
+        assert_eq!(statistics.generation, 0);
+        assert_eq!(simulation.ga.generation(), 1);
+        assert_eq!(simulation.age, 0);
+        
+        assert_eq!(simulation.train_generation(&mut rng).generation, 1);
+        
+        let steps = simulation.step_for(&mut rng, Duration::from_millis(5));
+        assert!(steps > 0);
+        assert_eq!(simulation.age, steps % 50);
+    }
+    
+    // Fingerprints of seeded runs; the same values must come out on every
+    // target, so a change here is a reproducibility break. CI runs it both
+    // natively and under `wasm-pack test --node libs/simulation`.
+    #[cfg(not(feature = "parallel"))]
+    fn fingerprint(simulation: &Simulation) -> u64 {
+        let world = simulation.world();
+        let values = world.animals().iter()
+            .flat_map(|animal| [animal.position().x, animal.position().y, math::angle(animal.rotation())])
+            .chain(world.foods().iter().flat_map(|food| [food.position().x, food.position().y]));
+        
+        values.fold(0xcbf29ce484222325, |hash, value| {
+            (hash ^ value.to_bits() as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+    
+    // `parallel` breeds on per-offspring streams, which is reproducible but a
+    // different trajectory from the sequential one wasm uses.
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[cfg(not(feature = "parallel"))]
+    fn seeded_runs_match_golden_trajectory() {
+        let (mut simulation, mut rng) = Simulation::with_seed(7);
+        assert_eq!(fingerprint(&simulation), 1263613543743789659);
+        
+        for _ in 0..simulation.config().generation_length + 10 {
+            simulation.step(&mut rng);
+        }
+        assert_eq!(fingerprint(&simulation), 11991818541135044211);
+        
+        let (replay, _) = Simulation::with_seed(7);
+        let (other, _) = Simulation::with_seed(8);
+        assert_eq!(fingerprint(&replay), fingerprint(&Simulation::with_seed(7).0));
+        assert_ne!(fingerprint(&replay), fingerprint(&other));
+    }
 }
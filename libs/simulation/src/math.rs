@@ -0,0 +1,18 @@
+use crate::*;
+use std::f32::consts::PI;
+
+// Rotations go through `libm` so the same seed yields the same trajectory on
+// native and wasm targets, whose std trigonometry may round differently.
+pub(crate) fn rotation(angle: f32) -> na::Rotation2<f32> {
+    let (sin, cos) = libm::sincosf(angle);
+    na::Rotation2::from_matrix_unchecked(na::Matrix2::new(cos, -sin, sin, cos))
+}
+
+pub(crate) fn angle(rotation: &na::Rotation2<f32>) -> f32 {
+    let matrix = rotation.matrix();
+    libm::atan2f(matrix[(1, 0)], matrix[(0, 0)])
+}
+
+pub(crate) fn random_rotation(rng: &mut dyn RngCore) -> na::Rotation2<f32> {
+    rotation(rng.gen_range(-PI..PI))
+}
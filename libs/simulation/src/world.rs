@@ -31,7 +31,7 @@ impl<T> RandomQueue<T> {
     }
     
     pub fn push(&mut self, item: T, rng: &mut dyn RngCore) {
-        let index = rng.gen_range(0..self.items.len() + 1);
+        let index = ga::random_index(rng, self.items.len() + 1);
         self.items.insert(index, item);
     }
     
@@ -39,7 +39,7 @@ impl<T> RandomQueue<T> {
         if self.items.is_empty() {
             None
         } else {
-            let index = rng.gen_range(0..self.items.len());
+            let index = ga::random_index(rng, self.items.len());
             Some(self.items.remove(index))
         }
     }
@@ -50,6 +50,7 @@ impl<T> RandomQueue<T> {
 #[derive(Debug)]
 pub struct World {
    pub(crate) animals: Vec<Animal>,
+   pub(crate) predators: Vec<Animal>,
    pub(crate) foods: Vec<Food>,
 }
 
@@ -94,12 +95,13 @@ impl World {
     pub fn new() -> Self {
         Self {
             animals: vec![],
+            predators: vec![],
             foods: vec![],
         }
     }
-    pub fn random(&mut self, rng: &mut dyn RngCore) {
-        let animals = self.generate_poison(rng, 1.0, 1.0, 35, 0.1).into_iter().map(|position| Animal::new(position, rng.gen(), 0.002, rng)).collect();
-        let foods = self.generate_poison(rng, 1.0, 1.0, 50, 0.05).into_iter().map(|position| Food::new(position)).collect();
+    pub fn random(&mut self, rng: &mut dyn RngCore, config: &Config) {
+        let animals = self.generate_poison(rng, 1.0, 1.0, config.animals, config.animal_spacing).into_iter().map(|position| Animal::new(config, position, math::random_rotation(rng), rng)).collect();
+        let foods = self.generate_poison(rng, 1.0, 1.0, config.foods, config.food_spacing).into_iter().map(|position| Food::new(position)).collect();
         
         self.animals = animals;
         self.foods = foods;
@@ -110,6 +112,10 @@ impl World {
         &self.animals
     }
     
+    pub fn predators(&self) -> &Vec<Animal> {
+        &self.predators
+    }
+    
     pub fn foods(&self) -> &Vec<Food> {
         &self.foods
     }
@@ -37,11 +37,28 @@ function drawCircle(ctxt, x, y, size) {
   ctxt.fill();
 }
 
-const simulation = new sim.Simulation();
+// Pass ?seed=<n> to replay a run; the seed of every run is logged for bug reports.
+// ?config=<json> overrides the simulation config, e.g. to switch to a steady-state lifecycle.
+const params = new URLSearchParams(window.location.search);
+const seed = params.get('seed');
+const config = params.get('config');
+const simulation = new sim.Simulation(
+  seed === null ? undefined : BigInt(seed),
+  config === null ? undefined : config
+);
+console.log(`seed: ${simulation.seed()}`);
 
 const world = simulation.world();
 console.log(world);
 
+const statistics = document.getElementById('statistics')!;
+document.getElementById('skip-generation')!.onclick = () => {
+  const stats = simulation.train_generation();
+  statistics.textContent =
+    `generation ${stats.generation}: ` +
+    `min ${stats.min_fitness}, max ${stats.max_fitness}, avg ${stats.avg_fitness.toFixed(2)}`;
+};
+
 const viewportWidth = viewport.width;
 const viewportHeight = viewport.height;
 